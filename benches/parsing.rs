@@ -0,0 +1,47 @@
+//! Parsing throughput benchmarks, backed by real IGS products so
+//! regressions against representative file sizes/sampling rates are caught
+//! as new formats (like sp3d) are added.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sp3::prelude::SP3;
+use std::fs;
+
+mod support;
+use support::ProductCache;
+
+/// `(label, CDDIS-relative path, approximate epoch count)`, spanning the
+/// ultra-rapid/rapid/final product families at 5' and 15' sampling.
+const PRODUCTS: &[(&str, &str, usize)] = &[
+    ("ultra_rapid_15min", "2024/igu24230_00.sp3.gz", 96),
+    ("rapid_15min", "2024/igr24230.sp3.gz", 96),
+    ("final_15min", "2024/igs24230.sp3.gz", 96),
+    ("final_5min", "2024/igs24230_05min.sp3.gz", 288),
+];
+
+fn bench_from_file(c: &mut Criterion) {
+    let cache = ProductCache::new();
+    let mut group = c.benchmark_group("SP3::from_file");
+
+    for (label, relative_path, nb_epochs) in PRODUCTS {
+        let url = format!("https://cddis.nasa.gov/archive/gnss/products/{relative_path}");
+        let filename = relative_path.rsplit('/').next().unwrap_or(relative_path);
+        let path = cache.fetch(&url, filename);
+        let file_size = fs::metadata(&path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        group.throughput(Throughput::Bytes(file_size));
+        group.bench_with_input(BenchmarkId::new("bytes", label), &path, |b, path| {
+            b.iter(|| SP3::from_file(&path.to_string_lossy()).unwrap());
+        });
+
+        group.throughput(Throughput::Elements(*nb_epochs as u64));
+        group.bench_with_input(BenchmarkId::new("epochs", label), &path, |b, path| {
+            b.iter(|| SP3::from_file(&path.to_string_lossy()).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_from_file);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+//! Download-and-cache helper for benchmark fixtures.
+//!
+//! Representative SP3 products (ultra-rapid, rapid and final, at 5' and 15'
+//! sampling) are pulled from CDDIS on first run into a local cache
+//! directory and unpacked there, so later benchmark runs reuse the same
+//! files instead of re-downloading them.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A small on-disk cache of downloaded, decompressed SP3 products.
+pub struct ProductCache {
+    cache_dir: PathBuf,
+}
+
+impl ProductCache {
+    pub fn new() -> Self {
+        let cache_dir = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("sp3-bench-cache");
+        fs::create_dir_all(&cache_dir).expect("failed to create benchmark cache directory");
+        Self { cache_dir }
+    }
+    /// Returns the local (decompressed) path for `filename`, downloading and
+    /// unpacking it from `url` first if it is not already cached.
+    pub fn fetch(&self, url: &str, filename: &str) -> PathBuf {
+        let compressed_path = self.cache_dir.join(filename);
+        let decompressed_path = self.cache_dir.join(filename.trim_end_matches(".gz"));
+
+        if !decompressed_path.exists() {
+            if !compressed_path.exists() {
+                let bytes = ureq::get(url)
+                    .call()
+                    .unwrap_or_else(|e| panic!("failed to download {}: {}", url, e))
+                    .into_reader();
+                let mut file = fs::File::create(&compressed_path)
+                    .unwrap_or_else(|e| panic!("failed to create {:?}: {}", compressed_path, e));
+                std::io::copy(&mut std::io::BufReader::new(bytes), &mut file)
+                    .unwrap_or_else(|e| panic!("failed to save {}: {}", url, e));
+            }
+            if filename.ends_with(".gz") {
+                let compressed = fs::File::open(&compressed_path)
+                    .unwrap_or_else(|e| panic!("failed to open {:?}: {}", compressed_path, e));
+                let mut decoder = flate2::read::GzDecoder::new(compressed);
+                let mut out = fs::File::create(&decompressed_path).unwrap_or_else(|e| {
+                    panic!("failed to create {:?}: {}", decompressed_path, e)
+                });
+                std::io::copy(&mut decoder, &mut out)
+                    .unwrap_or_else(|e| panic!("failed to unpack {:?}: {}", compressed_path, e));
+            }
+        }
+        decompressed_path
+    }
+    /// Total size, in bytes, of every file currently held in the cache.
+    pub fn size(&self) -> u64 {
+        self.entries().map(|e| e.metadata().unwrap().len()).sum()
+    }
+    /// Number of files currently held in the cache.
+    pub fn num_files(&self) -> usize {
+        self.entries().count()
+    }
+    /// Wipes the cache directory, so the next [`Self::fetch`] re-downloads.
+    pub fn remove(&self) {
+        let _ = fs::remove_dir_all(&self.cache_dir);
+    }
+    fn entries(&self) -> impl Iterator<Item = fs::DirEntry> {
+        fs::read_dir(&self.cache_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+    }
+}
+
+impl Default for ProductCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+pub fn product_path(cache: &ProductCache, product: &str) -> PathBuf {
+    let url = format!("https://cddis.nasa.gov/archive/gnss/products/{product}");
+    let filename = Path::new(product)
+        .file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or(product);
+    cache.fetch(&url, filename)
+}
@@ -0,0 +1,331 @@
+//! Transparent decompression for compressed SP3 products, so [`crate::SP3::from_file`]
+//! can be pointed directly at the `.sp3.gz` / `.sp3.Z` archives distributed by IGS
+//! analysis centers.
+use crate::Errors;
+use std::io::Read;
+
+/// Returns true when `path` looks like a gzip-compressed file, by extension.
+pub(crate) fn is_gzip_path(path: &str) -> bool {
+    path.ends_with(".gz")
+}
+
+/// Returns true when `path` looks like a Unix `compress` (`.Z`) file, by extension.
+pub(crate) fn is_unix_compressed_path(path: &str) -> bool {
+    path.ends_with(".Z")
+}
+
+/// Returns true when `bytes` start with the gzip magic number (RFC 1952),
+/// regardless of what the source file was named.
+pub(crate) fn looks_like_gzip(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && bytes[0] == 0x1F && bytes[1] == 0x8B
+}
+
+/// Returns true when `bytes` start with the legacy Unix `compress` (`.Z`)
+/// magic number, regardless of what the source file was named.
+pub(crate) fn looks_like_unix_compressed(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && bytes[0] == 0x1F && bytes[1] == 0x9D
+}
+
+/// Wraps `reader` in a gzip decoder, requires the `flate2` feature.
+#[cfg(feature = "flate2")]
+pub(crate) fn gzip_decoder<R: Read>(reader: R) -> flate2::read::GzDecoder<R> {
+    flate2::read::GzDecoder::new(reader)
+}
+
+/// Reads and fully decodes a legacy Unix `compress` (LZW, `.Z`) stream.
+/// The result is buffered in memory, since the format does not support
+/// incremental/streaming decoding past the dictionary reconstruction.
+pub(crate) fn unix_decompress<R: Read>(mut reader: R) -> Result<Vec<u8>, Errors> {
+    let mut magic = [0_u8; 3];
+    reader
+        .read_exact(&mut magic)
+        .map_err(Errors::FileIOError)?;
+    if magic[0] != 0x1F || magic[1] != 0x9D {
+        return Err(Errors::Decompression(
+            "not a Unix compress (.Z) stream".to_string(),
+        ));
+    }
+
+    let max_bits = (magic[2] & 0x1F) as u32;
+    let block_mode = magic[2] & 0x80 != 0;
+    if !(9..=16).contains(&max_bits) {
+        return Err(Errors::Decompression(format!(
+            "unsupported .Z code size {max_bits}"
+        )));
+    }
+
+    // code 256 is reserved as a CLEAR code when the archive is block-mode
+    let first_free_code = if block_mode { 257_u32 } else { 256_u32 };
+
+    // `table[code]` holds (prefix code, suffix byte); codes 0..256 are the
+    // raw byte values, and new entries are appended as the stream is read
+    let mut table: Vec<(Option<u32>, u8)> = (0_u32..first_free_code)
+        .map(|c| (None, c as u8))
+        .collect();
+
+    let mut bits = BitReader::new(reader);
+    let mut code_size = 9_u32;
+    let mut prev_code: Option<u32> = None;
+    let mut output = Vec::new();
+
+    while let Some(code) = bits.read_bits(code_size).map_err(Errors::FileIOError)? {
+        if block_mode && code == 256 {
+            table.truncate(first_free_code as usize);
+            code_size = 9;
+            prev_code = None;
+            continue;
+        }
+
+        let entry = if (code as usize) < table.len() {
+            expand_code(&table, code)
+        } else if code as usize == table.len() && prev_code.is_some() {
+            // the KwKwK special case: the code refers to the entry about to
+            // be inserted, which is always prev + prev's own first byte
+            let mut entry = expand_code(&table, prev_code.unwrap());
+            let first = entry[0];
+            entry.push(first);
+            entry
+        } else {
+            return Err(Errors::Decompression("invalid LZW code".to_string()));
+        };
+
+        output.extend_from_slice(&entry);
+
+        if let Some(prev) = prev_code {
+            table.push((Some(prev), entry[0]));
+            if table.len() as u32 == (1 << code_size) && code_size < max_bits {
+                code_size += 1;
+            }
+        }
+        prev_code = Some(code);
+    }
+
+    Ok(output)
+}
+
+/// Walks the prefix chain for `code`, returning its expanded byte sequence.
+fn expand_code(table: &[(Option<u32>, u8)], code: u32) -> Vec<u8> {
+    let mut reversed = Vec::new();
+    let mut current = code;
+    loop {
+        let (prefix, suffix) = table[current as usize];
+        reversed.push(suffix);
+        match prefix {
+            Some(p) => current = p,
+            None => break,
+        }
+    }
+    reversed.reverse();
+    reversed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_sniffing_is_extension_based() {
+        assert!(is_gzip_path("product.sp3.gz"));
+        assert!(!is_gzip_path("product.sp3.Z"));
+        assert!(is_unix_compressed_path("product.sp3.Z"));
+        assert!(!is_unix_compressed_path("product.sp3.gz"));
+        assert!(!is_gzip_path("product.sp3"));
+        assert!(!is_unix_compressed_path("product.sp3"));
+    }
+
+    #[test]
+    fn magic_sniffing_ignores_the_file_name() {
+        assert!(looks_like_gzip(&[0x1F, 0x8B, 0x08]));
+        assert!(!looks_like_gzip(&[0x1F, 0x9D, 0x90]));
+        assert!(looks_like_unix_compressed(&[0x1F, 0x9D, 0x90]));
+        assert!(!looks_like_unix_compressed(&[0x1F, 0x8B, 0x08]));
+        assert!(!looks_like_gzip(b"#dV2023"));
+        assert!(!looks_like_unix_compressed(&[0x1F]));
+    }
+
+    #[test]
+    fn rejects_stream_without_z_magic() {
+        let err = unix_decompress(&b"not a .Z stream"[..]).unwrap_err();
+        assert!(matches!(err, Errors::Decompression(_)));
+    }
+
+    /// Packs `codes` as consecutive `code_size`-bit, LSB-first values, the
+    /// same order [`BitReader`] reads them back in, so a synthetic `.Z`
+    /// fixture can be built without depending on an external `compress(1)`.
+    fn pack_lsb_first(codes: &[u32], code_size: u32) -> Vec<u8> {
+        let mut buffer = 0_u32;
+        let mut nb_bits = 0_u32;
+        let mut out = Vec::new();
+        for &code in codes {
+            buffer |= code << nb_bits;
+            nb_bits += code_size;
+            while nb_bits >= 8 {
+                out.push((buffer & 0xFF) as u8);
+                buffer >>= 8;
+                nb_bits -= 8;
+            }
+        }
+        if nb_bits > 0 {
+            out.push((buffer & 0xFF) as u8);
+        }
+        out
+    }
+
+    #[test]
+    fn decompresses_literal_only_z_stream() {
+        // non-block-mode, 9-bit codes; 'H' (72) and 'i' (105) as the raw
+        // byte codes, with no back-references: the dictionary only gains
+        // one entry that is never referenced, so neither a code-width bump
+        // nor the KwKwK case is exercised.
+        let mut stream = vec![0x1F_u8, 0x9D, 9];
+        stream.extend(pack_lsb_first(&[72, 105], 9));
+
+        let decompressed = unix_decompress(stream.as_slice()).expect("valid synthetic .Z stream");
+        assert_eq!(decompressed, b"Hi");
+    }
+
+    /// Packs `(code, code_size)` pairs as consecutive LSB-first values of
+    /// their own width, mirroring [`BitReader`] but allowing the width to
+    /// grow mid-stream the way real `.Z` code-size bumps do.
+    fn pack_variable_lsb_first(codes: &[(u32, u32)]) -> Vec<u8> {
+        let mut buffer = 0_u32;
+        let mut nb_bits = 0_u32;
+        let mut out = Vec::new();
+        for &(code, code_size) in codes {
+            buffer |= code << nb_bits;
+            nb_bits += code_size;
+            while nb_bits >= 8 {
+                out.push((buffer & 0xFF) as u8);
+                buffer >>= 8;
+                nb_bits -= 8;
+            }
+        }
+        if nb_bits > 0 {
+            out.push((buffer & 0xFF) as u8);
+        }
+        out
+    }
+
+    /// Minimal non-block-mode LZW encoder, growing its dictionary and code
+    /// width with exactly the same rules as [`unix_decompress`], so it can
+    /// produce a synthetic `.Z` fixture that exercises dictionary growth
+    /// (and the KwKwK special case) without depending on `compress(1)`.
+    fn lzw_encode(input: &[u8], max_bits: u32) -> Vec<(u32, u32)> {
+        use std::collections::HashMap;
+
+        let mut table: HashMap<Vec<u8>, u32> = (0_u32..256).map(|c| (vec![c as u8], c)).collect();
+        let mut next_code = 256_u32;
+        let mut code_size = 9_u32;
+        let mut codes = Vec::new();
+        let mut w: Vec<u8> = Vec::new();
+
+        for &c in input {
+            let mut wc = w.clone();
+            wc.push(c);
+            if table.contains_key(&wc) {
+                w = wc;
+            } else {
+                codes.push((table[&w], code_size));
+                if next_code < (1_u32 << max_bits) {
+                    table.insert(wc, next_code);
+                    next_code += 1;
+                    if next_code > (1_u32 << code_size) && code_size < max_bits {
+                        code_size += 1;
+                    }
+                }
+                w = vec![c];
+            }
+        }
+        if !w.is_empty() {
+            codes.push((table[&w], code_size));
+        }
+        codes
+    }
+
+    #[test]
+    fn decompresses_stream_with_dictionary_growth_and_kwkwk() {
+        // a short period repeated many times is the textbook case that
+        // forces the KwKwK special case (the encoder re-emits a phrase it
+        // only just added), and with enough repeats the dictionary grows
+        // past 511 entries, exercising the 9-to-10-bit code size bump
+        let input = b"ab".repeat(20000);
+        let max_bits = 10_u32;
+        let codes = lzw_encode(&input, max_bits);
+        assert!(
+            codes.iter().any(|&(_, size)| size == 10),
+            "fixture should force at least one code-width bump to 10 bits"
+        );
+
+        let mut stream = vec![0x1F_u8, 0x9D, max_bits as u8];
+        stream.extend(pack_variable_lsb_first(&codes));
+
+        let decompressed =
+            unix_decompress(stream.as_slice()).expect("valid synthetic .Z stream with dictionary growth");
+        assert_eq!(decompressed, input);
+    }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn gzip_round_trips_through_from_reader() {
+        use crate::prelude::*;
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::collections::BTreeSet;
+        use std::io::{BufReader, Cursor, Write};
+        use std::str::FromStr;
+
+        let epoch = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let sp3 = SP3 {
+            epoch: BTreeSet::from([epoch]),
+            ..Default::default()
+        };
+        let mut plain = Vec::new();
+        sp3.to_writer(&mut plain).unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&plain).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        // sanity check: the bytes we produced really do carry the gzip magic
+        assert!(looks_like_gzip(&gzipped));
+
+        let from_gz = SP3::from_reader(BufReader::new(Cursor::new(gzipped)))
+            .expect("SP3::from_reader should transparently decompress gzip content");
+        let from_plain = SP3::from_reader(BufReader::new(Cursor::new(plain)))
+            .expect("uncompressed reference parse");
+        assert_eq!(from_gz.epoch, from_plain.epoch);
+    }
+}
+
+/// Minimal LSB-first bit reader, matching the bit order `compress(1)` packs
+/// variable-width codes with.
+struct BitReader<R: Read> {
+    inner: R,
+    buffer: u32,
+    nb_bits: u32,
+}
+
+impl<R: Read> BitReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: 0,
+            nb_bits: 0,
+        }
+    }
+    fn read_bits(&mut self, n: u32) -> std::io::Result<Option<u32>> {
+        while self.nb_bits < n {
+            let mut byte = [0_u8; 1];
+            if self.inner.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            self.buffer |= (byte[0] as u32) << self.nb_bits;
+            self.nb_bits += 8;
+        }
+        let mask = (1_u32 << n) - 1;
+        let value = self.buffer & mask;
+        self.buffer >>= n;
+        self.nb_bits -= n;
+        Ok(Some(value))
+    }
+}
@@ -111,13 +111,11 @@ impl std::str::FromStr for DataUsed {
                 inner: vec![DataUsedUnitary::ComplexMix],
             })
         } else if content.contains('+') {
-            let offset = content.find('+').unwrap();
-            Ok(Self {
-                inner: vec![
-                    DataUsedUnitary::from_str(&content[..offset])?,
-                    DataUsedUnitary::from_str(&content[offset + 1..])?,
-                ],
-            })
+            let inner = content
+                .split('+')
+                .map(DataUsedUnitary::from_str)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Self { inner })
         } else {
             Ok(Self {
                 inner: vec![DataUsedUnitary::from_str(content)?],
@@ -128,24 +126,23 @@ impl std::str::FromStr for DataUsed {
 
 impl std::fmt::Display for DataUsed {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let len = self.inner.len();
-        if len == 1 {
-            f.write_str(&format!("{}", self.inner[0]))
-        } else if len > 1 {
-            f.write_str(&format!("{}+{}", self.inner[0], self.inner[1]))
-        } else {
-            Ok(())
-        }
+        let strings: Vec<String> = self.inner.iter().map(DataUsedUnitary::to_string).collect();
+        f.write_str(&strings.join("+"))
     }
 }
 
 impl DataUsed {
+    /// Creates an empty [`DataUsed`] combination.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Appends a [`DataUsedUnitary`] to this combination.
+    pub fn with(mut self, unitary: DataUsedUnitary) -> Self {
+        self.inner.push(unitary);
+        self
+    }
     pub fn complex_combination(&self) -> bool {
-        if self.inner.is_empty() {
-            self.inner[0] == DataUsedUnitary::ComplexMix
-        } else {
-            false
-        }
+        self.inner.first() == Some(&DataUsedUnitary::ComplexMix)
     }
     pub fn combination(&self) -> Option<(DataUsedUnitary, DataUsedUnitary)> {
         if self.inner.len() == 2 {
@@ -161,6 +158,22 @@ impl DataUsed {
             None
         }
     }
+    /// Returns an Iterator over the [`DataUsedUnitary`] codes forming this combination.
+    pub fn iter(&self) -> impl Iterator<Item = &DataUsedUnitary> + '_ {
+        self.inner.iter()
+    }
+    /// Returns true if `unitary` is part of this combination.
+    pub fn contains(&self, unitary: DataUsedUnitary) -> bool {
+        self.inner.contains(&unitary)
+    }
+    /// Returns the number of [`DataUsedUnitary`] codes forming this combination.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+    /// Returns true if this combination carries no [`DataUsedUnitary`] code.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -265,4 +278,32 @@ mod test {
             );
         }
     }
+    #[test]
+    fn ternary_combination_from_str() {
+        let parsed = DataUsed::from_str("u+du+U").unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert!(parsed.contains(DataUsedUnitary::UndifferencedPhase));
+        assert!(parsed.contains(DataUsedUnitary::UndifferencedCode));
+        assert!(!parsed.contains(DataUsedUnitary::ComplexMix));
+        assert_eq!(parsed.to_string(), "u+du+U");
+    }
+    #[test]
+    fn complex_combination() {
+        let mixed = DataUsed::from_str("MIXED").unwrap();
+        assert!(mixed.complex_combination());
+
+        let not_mixed = DataUsed::from_str("u").unwrap();
+        assert!(!not_mixed.complex_combination());
+
+        let empty = DataUsed::new();
+        assert!(!empty.complex_combination());
+        assert!(empty.is_empty());
+    }
+    #[test]
+    fn builder() {
+        let built = DataUsed::new()
+            .with(DataUsedUnitary::UndifferencedPhase)
+            .with(DataUsedUnitary::UndifferencedPhaseDerivative);
+        assert_eq!(built, DataUsed::from_str("u+du").unwrap());
+    }
 }
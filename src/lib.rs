@@ -3,7 +3,7 @@
 
 use hifitime::{Duration, Epoch, TimeScale};
 use rinex::prelude::{Constellation, Sv};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use std::str::FromStr;
 use thiserror::Error;
@@ -11,11 +11,14 @@ use thiserror::Error;
 #[cfg(test)]
 mod tests;
 
+mod compress;
 mod data_used;
 mod header;
 mod merge;
 mod position;
 mod reader;
+mod syntax_error;
+mod validate;
 mod velocity;
 mod version;
 mod writer;
@@ -34,7 +37,7 @@ use velocity::{velocity_entry, ClockRateRecord, VelocityEntry, VelocityRecord};
 use version::Version;
 
 use reader::BufferedReader;
-use std::io::{BufRead, Write};
+use std::io::{BufRead, Read, Write};
 use writer::BufferedWriter;
 
 #[cfg(feature = "serde")]
@@ -45,15 +48,22 @@ use serde::{Deserialize, Serialize};
  */
 type Vector3D = (f64, f64, f64);
 
+/// SP3 "bad or absent" sentinel value, used in place of a genuine
+/// position/clock sample when the estimate is unavailable.
+const BAD_OR_ABSENT: f64 = 999999.999999;
+
 pub mod prelude {
     pub use crate::version::Version;
     //pub use rinex::{Sv, Constellation};
     pub use crate::data_used::DataUsedUnitary;
-    pub use crate::{DataType, OrbitType, SP3};
+    pub use crate::{Cursor, DataType, Frame, MergeStrategy, OrbitType, SP3};
+    pub use crate::{SyntaxError, ValidationReport};
     pub use hifitime::{Duration, Epoch, TimeScale};
 }
 
-pub use merge::Merge;
+pub use merge::{Merge, MergeStrategy};
+pub use syntax_error::SyntaxError;
+pub use validate::ValidationReport;
 
 fn file_descriptor(content: &str) -> bool {
     content.starts_with("%c")
@@ -143,6 +153,36 @@ impl std::str::FromStr for OrbitType {
     }
 }
 
+/// Reference frame a position or velocity vector is expressed in.
+/// SP3 files natively carry positions in an Earth-fixed frame (`Ecef`,
+/// typically IGS/ITRF); `Eci` is obtained through a simplified rotation,
+/// see [`SP3::sv_position_eci`].
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Frame {
+    #[default]
+    Ecef,
+    Eci,
+}
+
+/// Tracks how far a consumer has advanced through an [`SP3`] record's epochs,
+/// by insertion ordinal rather than [`Epoch`], so it stays correct across
+/// repeated [`Merge::merge_mut`] calls even when `rhs` contributes epochs out
+/// of chronological order. See [`SP3::iter_since`].
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    last_seen: Option<u64>,
+}
+
+/// Position/clock/velocity entries newly observed since a [`Cursor`]'s last
+/// advance, as returned by [`SP3::iter_since`].
+#[derive(Default, Clone, Debug)]
+pub struct CursorEntries {
+    pub position: Vec<(Epoch, Sv, Vector3D)>,
+    pub clock: Vec<(Epoch, Sv, f64)>,
+    pub velocities: Vec<(Epoch, Sv, Vector3D)>,
+}
+
 /*
  * Comments contained in file
  */
@@ -179,7 +219,9 @@ pub struct SP3 {
     /// [`Epoch`]s where at least one position
     /// or one clock data is provided. Epochs are expressed UTC time,
     /// either directly if provided as such, or internally converted.
-    pub epoch: Vec<Epoch>,
+    /// Backed by a [`BTreeSet`] so epochs are intrinsically ordered and
+    /// membership/insertion stay O(log n), without a trailing sort.
+    pub epoch: BTreeSet<Epoch>,
     /// Returns sampling interval, ie., time between successive [`Epoch`]s.
     pub epoch_interval: Duration,
     /// Satellite Vehicles
@@ -194,6 +236,11 @@ pub struct SP3 {
     pub clock_rate: ClockRateRecord,
     /// File header comments, stored as is.
     pub comments: Comments,
+    /// Insertion-order ordinal assigned to each newly observed [`Epoch`],
+    /// used by [`Cursor`]/[`Self::iter_since`] to resume consumption after a
+    /// merge regardless of the chronological order in which `merge_mut`
+    /// introduced epochs.
+    epoch_ordinals: BTreeMap<Epoch, u64>,
 }
 
 #[derive(Debug, Error)]
@@ -206,6 +253,18 @@ pub enum Errors {
     ConstellationParsingError(#[from] rinex::constellation::Error),
     #[error("file i/o error")]
     FileIOError(#[from] std::io::Error),
+    #[error("corrupted binary cache: {0}")]
+    CorruptedBinary(String),
+    #[error("decompression error: {0}")]
+    Decompression(String),
+    #[error("{} syntax error(s) found while parsing SP3 file", .0.len())]
+    Syntax(Vec<SyntaxError>),
+    #[cfg(feature = "msgpack")]
+    #[error("msgpack encoding error")]
+    MsgPackEncodingError(#[from] rmp_serde::encode::Error),
+    #[cfg(feature = "msgpack")]
+    #[error("msgpack decoding error")]
+    MsgPackDecodingError(#[from] rmp_serde::decode::Error),
 }
 
 #[derive(Debug, PartialEq, Error)]
@@ -290,11 +349,72 @@ fn parse_epoch(content: &str, time_scale: TimeScale) -> Result<Epoch, ParsingErr
 }
 
 impl SP3 {
-    /// Parses given SP3 file, with possible seamless
-    /// .gz decompression, if compiled with the "flate2" feature.
+    /// Parses given SP3 file. Transparently decompresses it beforehand when
+    /// its extension looks like gzip (`.gz`, requires the "flate2" feature)
+    /// or legacy Unix `compress` (`.Z`), so callers can point this directly
+    /// at the archives distributed by IGS analysis centers (CDDIS, IGN, ...)
+    /// without a separate decompression step. Falls back to magic-byte
+    /// sniffing (see [`Self::from_reader`]) for files compressed but not
+    /// named with either extension.
     pub fn from_file(path: &str) -> Result<Self, Errors> {
+        if compress::is_gzip_path(path) {
+            #[cfg(feature = "flate2")]
+            {
+                let fd = std::fs::File::open(path)?;
+                let decoder = compress::gzip_decoder(fd);
+                return Self::from_reader(std::io::BufReader::new(decoder));
+            }
+            #[cfg(not(feature = "flate2"))]
+            {
+                return Err(Errors::Decompression(
+                    "gzip decompression requires the \"flate2\" feature".to_string(),
+                ));
+            }
+        }
+        if compress::is_unix_compressed_path(path) {
+            let fd = std::fs::File::open(path)?;
+            let decompressed = compress::unix_decompress(fd)?;
+            return Self::from_reader(std::io::BufReader::new(std::io::Cursor::new(
+                decompressed,
+            )));
+        }
         let reader = BufferedReader::new(path)?;
-
+        Self::from_reader(reader)
+    }
+    /// Parses [`SP3`] content from any [`BufRead`] implementor, so callers
+    /// are not restricted to `BufferedReader`'s file-backed decompression
+    /// path and can, for instance, parse straight out of an in-memory buffer,
+    /// a network stream, or their own decompressor. Transparently decompresses
+    /// gzip or legacy Unix `compress` (`.Z`) content, detected from its magic
+    /// number rather than a filename, so this behaves consistently whether
+    /// `reader` was opened by [`Self::from_file`] or built by the caller.
+    pub fn from_reader<R: BufRead>(mut reader: R) -> Result<Self, Errors> {
+        let magic = reader.fill_buf()?;
+        if compress::looks_like_gzip(magic) {
+            #[cfg(feature = "flate2")]
+            {
+                let decoder = compress::gzip_decoder(reader);
+                return Self::from_reader(std::io::BufReader::new(decoder));
+            }
+            #[cfg(not(feature = "flate2"))]
+            {
+                return Err(Errors::Decompression(
+                    "gzip decompression requires the \"flate2\" feature".to_string(),
+                ));
+            }
+        }
+        if compress::looks_like_unix_compressed(magic) {
+            let decompressed = compress::unix_decompress(reader)?;
+            return Self::from_reader(std::io::BufReader::new(std::io::Cursor::new(
+                decompressed,
+            )));
+        }
+        Self::from_plain_reader(reader)
+    }
+    /// Parses already-decompressed [`SP3`] text, shared by every
+    /// [`Self::from_reader`] dispatch branch once decompression (if any) has
+    /// happened.
+    fn from_plain_reader<R: BufRead>(reader: R) -> Result<Self, Errors> {
         let mut version = Version::default();
         let mut data_used = DataUsed::default();
         let mut data_type = DataType::default();
@@ -319,8 +439,13 @@ impl SP3 {
 
         let mut epoch = Epoch::default();
         let mut epochs: Vec<Epoch> = Vec::new();
+        let mut errors: Vec<SyntaxError> = Vec::new();
+        let mut last_line_no = 0;
+        let mut found_eof = false;
 
-        for line in reader.lines() {
+        for (line_index, line) in reader.lines().enumerate() {
+            let line_no = line_index + 1;
+            last_line_no = line_no;
             let line = line.unwrap();
             let line = line.trim();
             if sp3_comment(line) {
@@ -328,40 +453,87 @@ impl SP3 {
                 continue;
             }
             if end_of_file(line) {
+                found_eof = true;
                 break;
             }
             if is_header_line1(line) && !is_header_line2(line) {
-                let l1 = Line1::from_str(line)?;
-                (
-                    version,
-                    data_type,
-                    data_used,
-                    coord_system,
-                    orbit_type,
-                    agency,
-                ) = l1.to_parts();
+                match Line1::from_str(line) {
+                    Ok(l1) => {
+                        (
+                            version,
+                            data_type,
+                            data_used,
+                            coord_system,
+                            orbit_type,
+                            agency,
+                        ) = l1.to_parts();
+                        // SP3-a predates the SP3-b velocity record extension
+                        if version == Version::A && data_type == DataType::Velocity {
+                            errors.push(SyntaxError {
+                                message: "SP3-a does not support velocity records".to_string(),
+                                line: line_no,
+                                span: 0..line.len(),
+                            });
+                        }
+                    }
+                    Err(e) => errors.push(SyntaxError {
+                        message: e.to_string(),
+                        line: line_no,
+                        span: 0..line.len(),
+                    }),
+                }
             }
             if is_header_line2(line) {
-                let l2 = Line2::from_str(line)?;
-                (week_counter, epoch_interval, mjd_start) = l2.to_parts();
+                match Line2::from_str(line) {
+                    Ok(l2) => (week_counter, epoch_interval, mjd_start) = l2.to_parts(),
+                    Err(e) => errors.push(SyntaxError {
+                        message: e.to_string(),
+                        line: line_no,
+                        span: 0..line.len(),
+                    }),
+                }
             }
             if file_descriptor(line) {
                 if line.len() < 60 {
-                    return Err(Errors::ParsingError(ParsingError::MalformedDescriptor(
-                        line.to_string(),
-                    )));
-                }
-
-                if pc_count == 0 {
-                    constellation = Constellation::from_str(line[3..4].trim())?;
-                    time_system = TimeScale::from_str(line[9..12].trim())?;
+                    errors.push(SyntaxError {
+                        message: ParsingError::MalformedDescriptor(line.to_string()).to_string(),
+                        line: line_no,
+                        span: 0..line.len(),
+                    });
+                } else {
+                    if pc_count == 0 {
+                        match Constellation::from_str(line[3..4].trim()) {
+                            Ok(c) => constellation = c,
+                            Err(e) => errors.push(SyntaxError {
+                                message: e.to_string(),
+                                line: line_no,
+                                span: 3..4,
+                            }),
+                        }
+                        match TimeScale::from_str(line[9..12].trim()) {
+                            Ok(t) => time_system = t,
+                            Err(e) => errors.push(SyntaxError {
+                                message: e.to_string(),
+                                line: line_no,
+                                span: 9..12,
+                            }),
+                        }
+                    }
+                    pc_count += 1;
                 }
-
-                pc_count += 1;
             }
             if new_epoch(line) {
-                epoch = parse_epoch(&line[3..], time_system)?;
-                epochs.push(epoch);
+                match parse_epoch(&line[3..], time_system) {
+                    Ok(e) => {
+                        epoch = e;
+                        epochs.push(epoch);
+                    }
+                    Err(e) => errors.push(SyntaxError {
+                        message: e.to_string(),
+                        line: line_no,
+                        span: 3..line.len(),
+                    }),
+                }
             }
             if position_entry(line) {
                 if line.len() < 60 {
@@ -370,7 +542,17 @@ impl SP3 {
                      */
                     continue;
                 }
-                let entry = PositionEntry::from_str(line)?;
+                let entry = match PositionEntry::from_str(line) {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        errors.push(SyntaxError {
+                            message: e.to_string(),
+                            line: line_no,
+                            span: 0..line.len(),
+                        });
+                        continue;
+                    }
+                };
                 let (sv, (pos_x, pos_y, pos_z), clk) = entry.to_parts();
 
                 //TODO : move this into %c config frame
@@ -410,7 +592,17 @@ impl SP3 {
                      */
                     continue;
                 }
-                let entry = VelocityEntry::from_str(line)?;
+                let entry = match VelocityEntry::from_str(line) {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        errors.push(SyntaxError {
+                            message: e.to_string(),
+                            line: line_no,
+                            span: 0..line.len(),
+                        });
+                        continue;
+                    }
+                };
                 let (sv, (vel_x, vel_y, vel_z), clk) = entry.to_parts();
 
                 //TODO : move this into %c config frame
@@ -444,11 +636,29 @@ impl SP3 {
                 }
             }
         }
+        if !found_eof {
+            errors.push(SyntaxError {
+                message: "missing trailing EOF marker".to_string(),
+                line: last_line_no,
+                span: 0..0,
+            });
+        }
+        if !errors.is_empty() {
+            return Err(Errors::Syntax(errors));
+        }
+
+        let epoch_ordinals = epochs
+            .iter()
+            .enumerate()
+            .map(|(ordinal, e)| (*e, ordinal as u64))
+            .collect();
+        let epoch: BTreeSet<Epoch> = epochs.into_iter().collect();
+
         Ok(Self {
             version,
             data_type,
             data_used,
-            epoch: epochs,
+            epoch,
             time_system,
             constellation,
             coord_system,
@@ -463,12 +673,335 @@ impl SP3 {
             clock,
             clock_rate,
             comments,
+            epoch_ordinals,
         })
     }
+    /// Same as [`Self::from_file`], but parses the per-epoch position/clock/
+    /// velocity records in parallel with `rayon`, requires the "rayon"
+    /// feature. The file is first split into independent chunks at every
+    /// epoch (`*`) marker, which are then parsed concurrently; chunk order
+    /// is preserved regardless of thread scheduling, so the resulting
+    /// [`SP3`] is identical to what [`Self::from_file`] would produce. Only
+    /// plain-text files are supported here; compressed archives should be
+    /// decompressed first and passed through [`Self::from_reader`].
+    #[cfg(feature = "rayon")]
+    pub fn from_file_parallel(path: &str) -> Result<Self, Errors> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_str_parallel(&content)
+    }
+    /// Parses an already-loaded SP3 text buffer, parallelizing the
+    /// per-epoch records as described in [`Self::from_file_parallel`].
+    #[cfg(feature = "rayon")]
+    fn from_str_parallel(content: &str) -> Result<Self, Errors> {
+        use rayon::prelude::*;
+
+        let all_lines: Vec<&str> = content.lines().map(str::trim).collect();
+        let all_lines_len = all_lines.len();
+        let found_eof = all_lines.iter().any(|l| end_of_file(l));
+        let lines: Vec<&str> = all_lines.into_iter().take_while(|l| !end_of_file(l)).collect();
+
+        let mut version = Version::default();
+        let mut data_used = DataUsed::default();
+        let mut data_type = DataType::default();
+        let mut time_system = TimeScale::default();
+        let mut constellation = Constellation::default();
+        let mut pc_count = 0_u8;
+        let mut coord_system = String::from("Unknown");
+        let mut orbit_type = OrbitType::default();
+        let mut agency = String::from("Unknown");
+        let mut week_counter = (0_u32, 0_f64);
+        let mut epoch_interval = Duration::default();
+        let mut mjd_start = (0_u32, 0_f64);
+        let mut comments = Comments::new();
+
+        let mut errors: Vec<SyntaxError> = Vec::new();
+
+        // header lines are few and sequential by nature: parse them first,
+        // on the main thread, then hand the epoch blocks off to rayon
+        let first_epoch_index = lines
+            .iter()
+            .position(|line| new_epoch(line))
+            .unwrap_or(lines.len());
+
+        for (line_index, line) in lines[..first_epoch_index].iter().enumerate() {
+            let line_no = line_index + 1;
+            if sp3_comment(line) {
+                comments.push(line[3..].to_string());
+                continue;
+            }
+            if is_header_line1(line) && !is_header_line2(line) {
+                match Line1::from_str(line) {
+                    Ok(l1) => {
+                        (
+                            version,
+                            data_type,
+                            data_used,
+                            coord_system,
+                            orbit_type,
+                            agency,
+                        ) = l1.to_parts();
+                        // SP3-a predates the SP3-b velocity record extension
+                        if version == Version::A && data_type == DataType::Velocity {
+                            errors.push(SyntaxError {
+                                message: "SP3-a does not support velocity records".to_string(),
+                                line: line_no,
+                                span: 0..line.len(),
+                            });
+                        }
+                    }
+                    Err(e) => errors.push(SyntaxError {
+                        message: e.to_string(),
+                        line: line_no,
+                        span: 0..line.len(),
+                    }),
+                }
+            }
+            if is_header_line2(line) {
+                match Line2::from_str(line) {
+                    Ok(l2) => (week_counter, epoch_interval, mjd_start) = l2.to_parts(),
+                    Err(e) => errors.push(SyntaxError {
+                        message: e.to_string(),
+                        line: line_no,
+                        span: 0..line.len(),
+                    }),
+                }
+            }
+            if file_descriptor(line) {
+                if line.len() < 60 {
+                    errors.push(SyntaxError {
+                        message: ParsingError::MalformedDescriptor(line.to_string()).to_string(),
+                        line: line_no,
+                        span: 0..line.len(),
+                    });
+                } else {
+                    if pc_count == 0 {
+                        match Constellation::from_str(line[3..4].trim()) {
+                            Ok(c) => constellation = c,
+                            Err(e) => errors.push(SyntaxError {
+                                message: e.to_string(),
+                                line: line_no,
+                                span: 3..4,
+                            }),
+                        }
+                        match TimeScale::from_str(line[9..12].trim()) {
+                            Ok(t) => time_system = t,
+                            Err(e) => errors.push(SyntaxError {
+                                message: e.to_string(),
+                                line: line_no,
+                                span: 9..12,
+                            }),
+                        }
+                    }
+                    pc_count += 1;
+                }
+            }
+        }
+
+        // split the remainder into one chunk per epoch marker
+        let mut chunk_bounds = Vec::new();
+        let mut indices = lines[first_epoch_index..]
+            .iter()
+            .enumerate()
+            .filter_map(|(i, l)| new_epoch(l).then_some(first_epoch_index + i))
+            .collect::<Vec<_>>();
+        indices.push(lines.len());
+        for window in indices.windows(2) {
+            chunk_bounds.push((window[0], window[1]));
+        }
+
+        type EpochChunk = (
+            Option<Epoch>,
+            BTreeMap<Sv, Vector3D>,
+            BTreeMap<Sv, f64>,
+            BTreeMap<Sv, Vector3D>,
+            BTreeMap<Sv, f64>,
+            Vec<Sv>,
+            Vec<SyntaxError>,
+        );
+
+        let parsed: Vec<EpochChunk> = chunk_bounds
+            .par_iter()
+            .map(|(start, end)| Self::parse_epoch_chunk(&lines[*start..*end], *start + 1, time_system))
+            .collect();
+
+        let mut vehicles: Vec<Sv> = Vec::new();
+        let mut position = PositionRecord::default();
+        let mut velocities = VelocityRecord::default();
+        let mut clock = ClockRecord::default();
+        let mut clock_rate = ClockRateRecord::default();
+        let mut epochs: Vec<Epoch> = Vec::with_capacity(parsed.len());
+
+        for (epoch, pos_map, clk_map, vel_map, clk_rate_map, svs, chunk_errors) in parsed {
+            errors.extend(chunk_errors);
+            let epoch = match epoch {
+                Some(epoch) => epoch,
+                None => continue,
+            };
+            epochs.push(epoch);
+            for sv in svs {
+                if !vehicles.contains(&sv) {
+                    vehicles.push(sv);
+                }
+            }
+            if !pos_map.is_empty() {
+                position.insert(epoch, pos_map);
+            }
+            if !clk_map.is_empty() {
+                clock.insert(epoch, clk_map);
+            }
+            if !vel_map.is_empty() {
+                velocities.insert(epoch, vel_map);
+            }
+            if !clk_rate_map.is_empty() {
+                clock_rate.insert(epoch, clk_rate_map);
+            }
+        }
+
+        if !found_eof {
+            errors.push(SyntaxError {
+                message: "missing trailing EOF marker".to_string(),
+                line: all_lines_len,
+                span: 0..0,
+            });
+        }
+        if !errors.is_empty() {
+            return Err(Errors::Syntax(errors));
+        }
+
+        let epoch_ordinals = epochs
+            .iter()
+            .enumerate()
+            .map(|(ordinal, e)| (*e, ordinal as u64))
+            .collect();
+        let epoch: BTreeSet<Epoch> = epochs.into_iter().collect();
+
+        Ok(Self {
+            version,
+            data_type,
+            data_used,
+            epoch,
+            time_system,
+            constellation,
+            coord_system,
+            orbit_type,
+            agency,
+            week_counter,
+            epoch_interval,
+            mjd_start,
+            sv: vehicles,
+            position,
+            velocities,
+            clock,
+            clock_rate,
+            comments,
+            epoch_ordinals,
+        })
+    }
+    /// Parses a single epoch block (the `*` marker line, followed by its
+    /// position/velocity records) in isolation, so [`Self::from_str_parallel`]
+    /// can hand one of these to each `rayon` worker. Accumulates every
+    /// [`SyntaxError`] it runs into instead of bailing on the first one, the
+    /// same way [`Self::from_plain_reader`] does, so the parallel and
+    /// sequential parsers report malformed input identically.
+    #[cfg(feature = "rayon")]
+    fn parse_epoch_chunk(
+        lines: &[&str],
+        start_line_no: usize,
+        time_system: TimeScale,
+    ) -> (
+        Option<Epoch>,
+        BTreeMap<Sv, Vector3D>,
+        BTreeMap<Sv, f64>,
+        BTreeMap<Sv, Vector3D>,
+        BTreeMap<Sv, f64>,
+        Vec<Sv>,
+        Vec<SyntaxError>,
+    ) {
+        let mut errors = Vec::new();
+        let epoch = match parse_epoch(&lines[0][3..], time_system) {
+            Ok(epoch) => Some(epoch),
+            Err(e) => {
+                errors.push(SyntaxError {
+                    message: e.to_string(),
+                    line: start_line_no,
+                    span: 3..lines[0].len(),
+                });
+                None
+            }
+        };
+        let mut vehicles = Vec::new();
+        let mut position = BTreeMap::new();
+        let mut clock = BTreeMap::new();
+        let mut velocities = BTreeMap::new();
+        let mut clock_rate = BTreeMap::new();
+
+        for (offset, line) in lines[1..].iter().enumerate() {
+            let line_no = start_line_no + 1 + offset;
+            if position_entry(line) {
+                if line.len() < 60 {
+                    continue;
+                }
+                let entry = match PositionEntry::from_str(line) {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        errors.push(SyntaxError {
+                            message: e.to_string(),
+                            line: line_no,
+                            span: 0..line.len(),
+                        });
+                        continue;
+                    }
+                };
+                let (sv, (pos_x, pos_y, pos_z), clk) = entry.to_parts();
+                if !vehicles.contains(&sv) {
+                    vehicles.push(sv);
+                }
+                if pos_x != 0.0_f64 && pos_y != 0.0_f64 && pos_z != 0.0_f64 {
+                    position.insert(sv, (pos_x, pos_y, pos_z));
+                }
+                if let Some(clk) = clk {
+                    clock.insert(sv, clk);
+                }
+            }
+            if velocity_entry(line) {
+                if line.len() < 60 {
+                    continue;
+                }
+                let entry = match VelocityEntry::from_str(line) {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        errors.push(SyntaxError {
+                            message: e.to_string(),
+                            line: line_no,
+                            span: 0..line.len(),
+                        });
+                        continue;
+                    }
+                };
+                let (sv, (vel_x, vel_y, vel_z), clk) = entry.to_parts();
+                if !vehicles.contains(&sv) {
+                    vehicles.push(sv);
+                }
+                if vel_x != 0.0_f64 && vel_y != 0.0_f64 && vel_z != 0.0_f64 {
+                    velocities.insert(sv, (vel_x, vel_y, vel_z));
+                }
+                if let Some(clk) = clk {
+                    clock_rate.insert(sv, clk);
+                }
+            }
+        }
+        (epoch, position, clock, velocities, clock_rate, vehicles, errors)
+    }
     /// Generates SP3 file from Self's content
     pub fn to_file(&self, path: &str) -> Result<(), Errors> {
-        let mut content = String::with_capacity(80);
         let mut writer = BufferedWriter::new(path)?;
+        self.to_writer(&mut writer)
+    }
+    /// Writes [`SP3`] content to any [`Write`] implementor, so callers are
+    /// not restricted to writing plain files and can, for instance, stream
+    /// into a `Vec<u8>`, a socket, or their own compressor.
+    pub fn to_writer<W: Write>(&self, writer: &mut W) -> Result<(), Errors> {
+        let mut content = String::with_capacity(80);
         let first_epoch = self.first_epoch().unwrap();
         let (y, m, d, hh, mm, ss, ns) = first_epoch.to_gregorian_utc();
 
@@ -552,9 +1085,40 @@ impl SP3 {
                     },
                 );
             for (sv, pos) in pos {
-                writer.write(
-                    format!("P{} {:6.7} {:6.7} {:6.7}\n", sv, pos.0, pos.1, pos.2).as_bytes(),
-                )?;
+                if let Some(clk) = self.clock.get(&epoch).and_then(|map| map.get(&sv)) {
+                    writer.write(
+                        format!(
+                            "P{} {:6.7} {:6.7} {:6.7} {:6.7}\n",
+                            sv, pos.0, pos.1, pos.2, clk
+                        )
+                        .as_bytes(),
+                    )?;
+                } else {
+                    writer.write(
+                        format!("P{} {:6.7} {:6.7} {:6.7}\n", sv, pos.0, pos.1, pos.2).as_bytes(),
+                    )?;
+                }
+
+                if self.data_type == DataType::Velocity {
+                    if let Some(vel) = self.velocities.get(&epoch).and_then(|map| map.get(&sv)) {
+                        if let Some(clk_rate) =
+                            self.clock_rate.get(&epoch).and_then(|map| map.get(&sv))
+                        {
+                            writer.write(
+                                format!(
+                                    "V{} {:6.7} {:6.7} {:6.7} {:6.7}\n",
+                                    sv, vel.0, vel.1, vel.2, clk_rate
+                                )
+                                .as_bytes(),
+                            )?;
+                        } else {
+                            writer.write(
+                                format!("V{} {:6.7} {:6.7} {:6.7}\n", sv, vel.0, vel.1, vel.2)
+                                    .as_bytes(),
+                            )?;
+                        }
+                    }
+                }
             }
         }
         writer.write(format!("EOF").as_bytes())?;
@@ -571,11 +1135,11 @@ impl SP3 {
     }
     /// Returns first epoch
     pub fn first_epoch(&self) -> Option<Epoch> {
-        self.epoch.get(0).copied()
+        self.epoch.iter().next().copied()
     }
     /// Returns last epoch
     pub fn last_epoch(&self) -> Option<Epoch> {
-        self.epoch.last().copied()
+        self.epoch.iter().next_back().copied()
     }
     /// Returns a unique Sv iterator
     pub fn sv(&self) -> impl Iterator<Item = Sv> + '_ {
@@ -621,6 +1185,8 @@ impl SP3 {
     /// SP3 file, the earliest interpolatable Epoch is T0 + (order +1)*dt/2,
     /// and the latest is T(N-1) - (oder +1)*dt /2, where T0 is the first epoch,
     /// T(N-1) the last one, and dt the epoch interval.
+    /// Returns `None` if any sample within the window is the SP3 "bad/absent"
+    /// sentinel (999999.999999), since it must never contaminate the polynomial.
     /// See [Bibliography::Japhet2021].
     pub fn interpolate(&self, epoch: Epoch, sv: Sv, order: usize) -> Option<Vector3D> {
         let odd_order = order % 2 > 0;
@@ -652,6 +1218,15 @@ impl SP3 {
                 }
                 let offset = center - min_before;
 
+                // the SP3 "bad/absent" sentinel must never contaminate the polynomial
+                let window = &sv_data[offset..offset + order + 1];
+                if window
+                    .iter()
+                    .any(|(_, pos)| pos.0 == BAD_OR_ABSENT || pos.1 == BAD_OR_ABSENT || pos.2 == BAD_OR_ABSENT)
+                {
+                    return None;
+                }
+
                 let mut polynomials = Vector3D::default();
                 for i in 0..order + 1 {
                     let mut li = 1.0_f64;
@@ -673,17 +1248,607 @@ impl SP3 {
             None
         }
     }
-}
+    /// Interpolates SV position from both position and velocity records, using
+    /// Hermite interpolation instead of plain Lagrange. This requires the SP3
+    /// file to carry a [`VelocityRecord`], and yields a much better accuracy
+    /// (or usable results at a lower order) than [`Self::interpolate`], because
+    /// the polynomial is additionally constrained to match the known velocity
+    /// (first derivative) at every sample.
+    /// Uses the same centered window selection as [`Self::interpolate`], so
+    /// the same rules about the earliest/latest interpolatable Epoch apply.
+    /// Returns `None` as soon as a node in the window lacks a velocity sample.
+    pub fn interpolate_hermite(&self, epoch: Epoch, sv: Sv, order: usize) -> Option<Vector3D> {
+        let (position, _) = self.sv_position_interpolate_hermite(epoch, sv, order)?;
+        Some(position)
+    }
+    /// Same Hermite scheme as [`Self::interpolate_hermite`], additionally
+    /// returning the polynomial's analytic derivative (in km/s) as an
+    /// interpolated velocity.
+    pub fn sv_position_interpolate_hermite(
+        &self,
+        epoch: Epoch,
+        sv: Sv,
+        order: usize,
+    ) -> Option<(Vector3D, Vector3D)> {
+        let odd_order = order % 2 > 0;
+        let sv_position: Vec<_> = self
+            .sv_position()
+            .filter_map(|(e, svnn, pos)| if svnn == sv { Some((e, pos)) } else { None })
+            .collect();
 
-use merge::MergeError;
+        let center = sv_position
+            .iter()
+            .position(|(e, _)| (*e - epoch).abs() < self.epoch_interval)?;
 
-impl Merge for SP3 {
-    fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
+        // define window
+        let (min_before, min_after): (usize, usize) = match odd_order {
+            true => ((order + 1) / 2, (order + 1) / 2),
+            false => (order / 2, order / 2 + 1),
+        };
+        if center < min_before || sv_position.len() - center < min_after {
+            return None;
+        }
+        let offset = center - min_before;
+        let n = order + 1;
+        let t0 = sv_position[offset].0;
+
+        // the SP3 "bad/absent" sentinel must never contaminate the polynomial
+        let window = &sv_position[offset..offset + n];
+        if window
+            .iter()
+            .any(|(_, pos)| pos.0 == BAD_OR_ABSENT || pos.1 == BAD_OR_ABSENT || pos.2 == BAD_OR_ABSENT)
+        {
+            return None;
+        }
+
+        // gather the (t_i, p_i, v_i) nodes, converting velocity from
+        // 10^-1 m/s to km/s so it matches the km position unit
+        let mut nodes: Vec<(f64, Vector3D, Vector3D)> = Vec::with_capacity(n);
+        for i in 0..n {
+            let (t, pos) = sv_position[offset + i];
+            let (_, _, vel) = self
+                .sv_velocities()
+                .find(|(e, svnn, _)| *svnn == sv && *e == t)?;
+            if vel.0 == BAD_OR_ABSENT || vel.1 == BAD_OR_ABSENT || vel.2 == BAD_OR_ABSENT {
+                return None;
+            }
+            let vel_kms = (vel.0 * 1.0E-4, vel.1 * 1.0E-4, vel.2 * 1.0E-4);
+            nodes.push(((t - t0).to_seconds(), pos, vel_kms));
+        }
+
+        // duplicate every node: z_{2i} = z_{2i+1} = t_i
+        let size = 2 * n;
+        let mut z = vec![0.0_f64; size];
+        let mut table = vec![vec![Vector3D::default(); size]; size];
+        for i in 0..n {
+            z[2 * i] = nodes[i].0;
+            z[2 * i + 1] = nodes[i].0;
+            table[2 * i][0] = nodes[i].1;
+            table[2 * i + 1][0] = nodes[i].1;
+        }
+
+        // first column: known derivative on repeated nodes, divided difference elsewhere
+        for i in 1..size {
+            if i % 2 == 1 {
+                table[i][1] = nodes[i / 2].2;
+            } else {
+                let dz = z[i] - z[i - 1];
+                table[i][1] = (
+                    (table[i][0].0 - table[i - 1][0].0) / dz,
+                    (table[i][0].1 - table[i - 1][0].1) / dz,
+                    (table[i][0].2 - table[i - 1][0].2) / dz,
+                );
+            }
+        }
+
+        // remaining columns: ordinary divided differences
+        for j in 2..size {
+            for i in j..size {
+                let dz = z[i] - z[i - j];
+                table[i][j] = (
+                    (table[i][j - 1].0 - table[i - 1][j - 1].0) / dz,
+                    (table[i][j - 1].1 - table[i - 1][j - 1].1) / dz,
+                    (table[i][j - 1].2 - table[i - 1][j - 1].2) / dz,
+                );
+            }
+        }
+
+        // Newton form evaluation, using the table's diagonal, plus its
+        // term-by-term derivative for the interpolated velocity
+        let t = (epoch - t0).to_seconds();
+        let mut position = Vector3D::default();
+        let mut velocity = Vector3D::default();
+        let mut product = 1.0_f64;
+        let mut dproduct = 0.0_f64;
+        for i in 0..size {
+            let coeff = table[i][i];
+            position.0 += coeff.0 * product;
+            position.1 += coeff.1 * product;
+            position.2 += coeff.2 * product;
+            velocity.0 += coeff.0 * dproduct;
+            velocity.1 += coeff.1 * dproduct;
+            velocity.2 += coeff.2 * dproduct;
+            // d/dt [product_{k<=i} (t - z[k])] via the product rule
+            dproduct = dproduct * (t - z[i]) + product;
+            product *= t - z[i];
+        }
+        Some((position, velocity))
+    }
+    /// Returns the `[first + k*dt, last - k*dt]` Epoch bounds (with
+    /// `k = order / 2` and `dt` the file's `epoch_interval`) within which
+    /// [`Self::interpolate`], [`Self::interpolate_hermite`] and
+    /// [`Self::sv_clock_interpolate`] can actually return a value, so callers
+    /// can filter down to interpolatable epochs before calling them.
+    /// For odd orders, those methods need `(order + 1) / 2` samples before
+    /// the center, so they only return a value from `first + (k + 1)*dt`
+    /// onwards, one interval after this window's start.
+    /// Returns `None` if the file has no epoch.
+    pub fn interpolation_window(&self, order: usize) -> Option<(Epoch, Epoch)> {
+        let first = self.first_epoch()?;
+        let last = self.last_epoch()?;
+        let k = (order / 2) as f64;
+        let margin = Duration::from_seconds(self.epoch_interval.to_seconds() * k);
+        Some((first + margin, last - margin))
+    }
+    /// Interpolates the SV clock offset (in microseconds) at the desired
+    /// Epoch, using the same centered-window Lagrange scheme as
+    /// [`Self::interpolate`]. Returns `None` if any sample within the window
+    /// is the SP3 "bad/absent" clock sentinel (999999.999999), since it must
+    /// never contaminate the polynomial.
+    pub fn sv_clock_interpolate(&self, epoch: Epoch, sv: Sv, order: usize) -> Option<f64> {
+        let odd_order = order % 2 > 0;
+        let sv_data: Vec<_> = self
+            .sv_clock()
+            .filter_map(|(e, svnn, clk)| if svnn == sv { Some((e, clk)) } else { None })
+            .collect();
+
+        let center = sv_data
+            .iter()
+            .position(|(e, _)| (*e - epoch).abs() < self.epoch_interval)?;
+
+        // define window
+        let (min_before, min_after): (usize, usize) = match odd_order {
+            true => ((order + 1) / 2, (order + 1) / 2),
+            false => (order / 2, order / 2 + 1),
+        };
+        if center < min_before || sv_data.len() - center < min_after {
+            return None;
+        }
+        let offset = center - min_before;
+
+        // the SP3 "bad/absent" sentinel must never contaminate the polynomial
+        let window = &sv_data[offset..offset + order + 1];
+        if window.iter().any(|(_, clk)| *clk == BAD_OR_ABSENT) {
+            return None;
+        }
+
+        let mut result = 0.0_f64;
+        for i in 0..order + 1 {
+            let mut li = 1.0_f64;
+            for j in 0..order + 1 {
+                if j != i {
+                    li *= (epoch - sv_data[offset + j].0).to_seconds();
+                    li /= (sv_data[offset + i].0 - sv_data[offset + j].0).to_seconds();
+                }
+            }
+            result += sv_data[offset + i].1 * li;
+        }
+        Some(result)
+    }
+    /// Returns a new [`SP3`] restricted to the `[start, end]` inclusive Epoch
+    /// range. `position`, `velocities`, `clock` and `clock_rate` are trimmed
+    /// accordingly, and the `sv` list is recomputed so it only reflects
+    /// vehicles still present in the surviving window.
+    pub fn slice(&self, start: Epoch, end: Epoch) -> Self {
         let mut s = self.clone();
-        s.merge_mut(rhs)?;
-        Ok(s)
+        s.epoch.retain(|e| *e >= start && *e <= end);
+        s.position.retain(|e, _| *e >= start && *e <= end);
+        s.velocities.retain(|e, _| *e >= start && *e <= end);
+        s.clock.retain(|e, _| *e >= start && *e <= end);
+        s.clock_rate.retain(|e, _| *e >= start && *e <= end);
+        s.update_sv_list();
+        s
     }
-    fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
+    /// Returns a new [`SP3`] where successive Epochs are at least `dt` apart,
+    /// dropping intermediate samples. `epoch_interval` is updated to `dt`.
+    pub fn decimate_by_interval(&self, dt: Duration) -> Self {
+        let mut s = self.clone();
+        let mut last_kept: Option<Epoch> = None;
+        s.epoch.retain(|e| {
+            let keep = last_kept.map(|last| *e - last >= dt).unwrap_or(true);
+            if keep {
+                last_kept = Some(*e);
+            }
+            keep
+        });
+        let kept = s.epoch.clone();
+        s.position.retain(|e, _| kept.contains(e));
+        s.velocities.retain(|e, _| kept.contains(e));
+        s.clock.retain(|e, _| kept.contains(e));
+        s.clock_rate.retain(|e, _| kept.contains(e));
+        s.epoch_interval = dt;
+        s.update_sv_list();
+        s
+    }
+    /// Returns a new [`SP3`] keeping only every `r`-th Epoch, dropping the
+    /// others. `epoch_interval` is scaled by `r` accordingly.
+    pub fn decimate_by_ratio(&self, r: usize) -> Self {
+        let mut s = self.clone();
+        let r = r.max(1);
+        let kept: BTreeSet<Epoch> = s.epoch.iter().step_by(r).copied().collect();
+        s.epoch = kept.clone();
+        s.position.retain(|e, _| kept.contains(e));
+        s.velocities.retain(|e, _| kept.contains(e));
+        s.clock.retain(|e, _| kept.contains(e));
+        s.clock_rate.retain(|e, _| kept.contains(e));
+        s.epoch_interval = Duration::from_seconds(self.epoch_interval.to_seconds() * r as f64);
+        s.update_sv_list();
+        s
+    }
+    /*
+     * Recomputes the `sv` list from the surviving position/velocity records,
+     * used after an operation (slice, decimation) that may drop vehicles
+     */
+    fn update_sv_list(&mut self) {
+        let mut sv: Vec<Sv> = Vec::new();
+        for map in self.position.values() {
+            for s in map.keys() {
+                if !sv.contains(s) {
+                    sv.push(*s);
+                }
+            }
+        }
+        for map in self.velocities.values() {
+            for s in map.keys() {
+                if !sv.contains(s) {
+                    sv.push(*s);
+                }
+            }
+        }
+        sv.sort();
+        self.sv = sv;
+    }
+    /*
+     * Assigns the next insertion ordinal to `epoch`, if not already stamped
+     */
+    fn stamp_epoch(&mut self, epoch: Epoch) {
+        if !self.epoch_ordinals.contains_key(&epoch) {
+            let ordinal = self.epoch_ordinals.len() as u64;
+            self.epoch_ordinals.insert(epoch, ordinal);
+        }
+    }
+    /// Returns every position/clock/velocity entry whose [`Epoch`] was
+    /// inserted since `cursor` was last advanced, then advances `cursor` to
+    /// the latest ordinal seen. Call repeatedly after successive
+    /// [`Merge::merge_mut`] calls to consume only the newly appended data,
+    /// without depending on chronological order.
+    pub fn iter_since(&mut self, cursor: &mut Cursor) -> CursorEntries {
+        // `epoch`/`position`/etc are `pub`, so an epoch can land in `self`
+        // without ever going through `stamp_epoch` (e.g. a struct literal
+        // built directly instead of via `merge_mut`). Backfill an ordinal
+        // for any such epoch now, so it surfaces exactly once instead of
+        // being permanently invisible to `is_new` below.
+        let unstamped: Vec<Epoch> = self
+            .epoch
+            .iter()
+            .filter(|e| !self.epoch_ordinals.contains_key(e))
+            .copied()
+            .collect();
+        for epoch in unstamped {
+            self.stamp_epoch(epoch);
+        }
+
+        let last_seen = cursor.last_seen;
+        let is_new = |e: &Epoch| -> bool {
+            self.epoch_ordinals
+                .get(e)
+                .map(|ordinal| last_seen.map(|last| *ordinal > last).unwrap_or(true))
+                .unwrap_or(false)
+        };
+        let entries = CursorEntries {
+            position: self.sv_position().filter(|(e, _, _)| is_new(e)).collect(),
+            clock: self.sv_clock().filter(|(e, _, _)| is_new(e)).collect(),
+            velocities: self
+                .sv_velocities()
+                .filter(|(e, _, _)| is_new(e))
+                .collect(),
+        };
+        if let Some(max) = self.epoch_ordinals.values().copied().max() {
+            if last_seen.map(|last| max > last).unwrap_or(true) {
+                cursor.last_seen = Some(max);
+            }
+        }
+        entries
+    }
+    /// Sidereal Earth rotation rate, in rad/s, used by [`Self::sv_position_eci`].
+    const EARTH_ROTATION_RATE_RAD_S: f64 = 7.292_115_0E-5;
+    /// Rotates an ECEF position vector about the Z axis by the Earth rotation
+    /// angle elapsed between `reference` and `epoch`, turning it into a
+    /// simplified inertial (ECI) vector. This does not account for
+    /// precession, nutation or polar motion: it is a simplified rotation,
+    /// not a rigorous ECEF/ECI conversion.
+    fn ecef_to_eci(position: Vector3D, epoch: Epoch, reference: Epoch) -> Vector3D {
+        let theta = Self::EARTH_ROTATION_RATE_RAD_S * (epoch - reference).to_seconds();
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        (
+            cos_theta * position.0 - sin_theta * position.1,
+            sin_theta * position.0 + cos_theta * position.1,
+            position.2,
+        )
+    }
+    /// Returns the inertial (ECI) position of `sv` at `epoch`, obtained by
+    /// rotating the stored ECEF position about the Z axis by the Earth
+    /// rotation angle elapsed since the record's first Epoch. See
+    /// [`Self::ecef_to_eci`] for the scope of this simplified conversion.
+    pub fn sv_position_eci(&self, epoch: Epoch, sv: Sv) -> Option<Vector3D> {
+        let reference = self.first_epoch()?;
+        let (_, _, pos) = self
+            .sv_position()
+            .find(|(e, svnn, _)| *e == epoch && *svnn == sv)?;
+        Some(Self::ecef_to_eci(pos, epoch, reference))
+    }
+    /// Same as [`Self::interpolate`], but returns the position expressed in
+    /// the requested [`Frame`].
+    pub fn interpolate_in_frame(
+        &self,
+        epoch: Epoch,
+        sv: Sv,
+        order: usize,
+        frame: Frame,
+    ) -> Option<Vector3D> {
+        let pos = self.interpolate(epoch, sv, order)?;
+        match frame {
+            Frame::Ecef => Some(pos),
+            Frame::Eci => {
+                let reference = self.first_epoch()?;
+                Some(Self::ecef_to_eci(pos, epoch, reference))
+            }
+        }
+    }
+    /// Writes this record to a compact fixed little-endian binary layout:
+    /// a length-prefixed SV list, then one block per Epoch carrying a
+    /// presence bitmask (one bit per SV, so a genuinely absent SV is
+    /// distinguished from the SP3 sentinel) followed by the f64 triplets of
+    /// every SV flagged present. Reloading this is far faster than
+    /// re-tokenizing the original ASCII file.
+    pub fn to_binary<W: Write>(&self, w: &mut W) -> Result<(), Errors> {
+        let sv_list: Vec<Sv> = self.sv().collect();
+        w.write_all(&(sv_list.len() as u32).to_le_bytes())?;
+        for sv in &sv_list {
+            let name = sv.to_string();
+            w.write_all(&[name.len() as u8])?;
+            w.write_all(name.as_bytes())?;
+        }
+
+        w.write_all(&(self.epoch.len() as u32).to_le_bytes())?;
+        for epoch in self.epoch() {
+            let (y, m, d, hh, mm, ss, ns) = epoch.to_gregorian_utc();
+            w.write_all(&y.to_le_bytes())?;
+            w.write_all(&[m, d, hh, mm, ss])?;
+            w.write_all(&ns.to_le_bytes())?;
+
+            Self::write_bitmask_and_vectors(w, &sv_list, self.position.get(&epoch))?;
+            Self::write_bitmask_and_scalars(w, &sv_list, self.clock.get(&epoch))?;
+            if self.data_type == DataType::Velocity {
+                Self::write_bitmask_and_vectors(w, &sv_list, self.velocities.get(&epoch))?;
+                Self::write_bitmask_and_scalars(w, &sv_list, self.clock_rate.get(&epoch))?;
+            }
+        }
+        Ok(())
+    }
+    /*
+     * Writes one presence bitmask followed by the (x, y, z) triplet of every
+     * Sv (from `sv_list`) present in `map`, in `sv_list` order
+     */
+    fn write_bitmask_and_vectors<W: Write>(
+        w: &mut W,
+        sv_list: &[Sv],
+        map: Option<&BTreeMap<Sv, Vector3D>>,
+    ) -> Result<(), Errors> {
+        let mut bitmask = vec![0_u8; (sv_list.len() + 7) / 8];
+        if let Some(map) = map {
+            for (index, sv) in sv_list.iter().enumerate() {
+                if map.contains_key(sv) {
+                    bitmask[index / 8] |= 1 << (index % 8);
+                }
+            }
+        }
+        w.write_all(&bitmask)?;
+        if let Some(map) = map {
+            for sv in sv_list {
+                if let Some((x, y, z)) = map.get(sv) {
+                    w.write_all(&x.to_le_bytes())?;
+                    w.write_all(&y.to_le_bytes())?;
+                    w.write_all(&z.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+    /*
+     * Same as [`Self::write_bitmask_and_vectors`], for a single scalar per Sv
+     */
+    fn write_bitmask_and_scalars<W: Write>(
+        w: &mut W,
+        sv_list: &[Sv],
+        map: Option<&BTreeMap<Sv, f64>>,
+    ) -> Result<(), Errors> {
+        let mut bitmask = vec![0_u8; (sv_list.len() + 7) / 8];
+        if let Some(map) = map {
+            for (index, sv) in sv_list.iter().enumerate() {
+                if map.contains_key(sv) {
+                    bitmask[index / 8] |= 1 << (index % 8);
+                }
+            }
+        }
+        w.write_all(&bitmask)?;
+        if let Some(map) = map {
+            for sv in sv_list {
+                if let Some(value) = map.get(sv) {
+                    w.write_all(&value.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Reconstructs an [`SP3`] record previously written with [`Self::to_binary`].
+    /// Only the epoch/SV/position/clock/velocity tables are restored; header
+    /// metadata (agency, coordinate system, etc.) is not part of this format,
+    /// so the original [`DataType`] must be supplied by the caller to know
+    /// whether a velocity/clock-rate block follows each position/clock block.
+    pub fn from_binary<R: Read>(r: &mut R, data_type: DataType) -> Result<Self, Errors> {
+        let mut u32_buf = [0_u8; 4];
+
+        r.read_exact(&mut u32_buf)?;
+        let nb_sv = u32::from_le_bytes(u32_buf) as usize;
+        let mut sv_list = Vec::with_capacity(nb_sv);
+        for _ in 0..nb_sv {
+            let mut len_buf = [0_u8; 1];
+            r.read_exact(&mut len_buf)?;
+            let mut name_buf = vec![0_u8; len_buf[0] as usize];
+            r.read_exact(&mut name_buf)?;
+            let name = String::from_utf8(name_buf)
+                .map_err(|e| Errors::CorruptedBinary(e.to_string()))?;
+            let sv = Sv::from_str(&name).map_err(|_| Errors::CorruptedBinary(name))?;
+            sv_list.push(sv);
+        }
+
+        r.read_exact(&mut u32_buf)?;
+        let nb_epoch = u32::from_le_bytes(u32_buf) as usize;
+
+        let mut epoch = BTreeSet::new();
+        let mut epochs: Vec<Epoch> = Vec::with_capacity(nb_epoch);
+        let mut position = PositionRecord::default();
+        let mut clock = ClockRecord::default();
+        let mut velocities = VelocityRecord::default();
+        let mut clock_rate = ClockRateRecord::default();
+
+        for _ in 0..nb_epoch {
+            let mut y_buf = [0_u8; 4];
+            r.read_exact(&mut y_buf)?;
+            let y = i32::from_le_bytes(y_buf);
+            let mut rest = [0_u8; 5];
+            r.read_exact(&mut rest)?;
+            let [m, d, hh, mm, ss] = rest;
+            let mut ns_buf = [0_u8; 4];
+            r.read_exact(&mut ns_buf)?;
+            let ns = u32::from_le_bytes(ns_buf);
+
+            let e = Epoch::from_str(&format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09} UTC",
+                y, m, d, hh, mm, ss, ns
+            ))
+            .map_err(|_| Errors::CorruptedBinary("epoch".to_string()))?;
+            epoch.insert(e);
+            epochs.push(e);
+
+            if let Some(map) = Self::read_bitmask_and_vectors(r, &sv_list)? {
+                position.insert(e, map);
+            }
+            if let Some(map) = Self::read_bitmask_and_scalars(r, &sv_list)? {
+                clock.insert(e, map);
+            }
+            if data_type == DataType::Velocity {
+                if let Some(map) = Self::read_bitmask_and_vectors(r, &sv_list)? {
+                    velocities.insert(e, map);
+                }
+                if let Some(map) = Self::read_bitmask_and_scalars(r, &sv_list)? {
+                    clock_rate.insert(e, map);
+                }
+            }
+        }
+
+        let epoch_ordinals = epochs
+            .iter()
+            .enumerate()
+            .map(|(ordinal, e)| (*e, ordinal as u64))
+            .collect();
+
+        Ok(Self {
+            epoch,
+            sv: sv_list,
+            position,
+            clock,
+            velocities,
+            clock_rate,
+            data_type,
+            epoch_ordinals,
+            ..Self::default()
+        })
+    }
+    /*
+     * Reads one presence bitmask followed by the (x, y, z) triplet of every
+     * flagged Sv, returning `None` when no Sv was present in this block
+     */
+    fn read_bitmask_and_vectors<R: Read>(
+        r: &mut R,
+        sv_list: &[Sv],
+    ) -> Result<Option<BTreeMap<Sv, Vector3D>>, Errors> {
+        let mut bitmask = vec![0_u8; (sv_list.len() + 7) / 8];
+        r.read_exact(&mut bitmask)?;
+        let mut map = BTreeMap::new();
+        for (index, sv) in sv_list.iter().enumerate() {
+            if bitmask[index / 8] & (1 << (index % 8)) != 0 {
+                let mut buf = [0_u8; 8];
+                r.read_exact(&mut buf)?;
+                let x = f64::from_le_bytes(buf);
+                r.read_exact(&mut buf)?;
+                let y = f64::from_le_bytes(buf);
+                r.read_exact(&mut buf)?;
+                let z = f64::from_le_bytes(buf);
+                map.insert(*sv, (x, y, z));
+            }
+        }
+        Ok(if map.is_empty() { None } else { Some(map) })
+    }
+    /*
+     * Same as [`Self::read_bitmask_and_vectors`], for a single scalar per Sv
+     */
+    fn read_bitmask_and_scalars<R: Read>(
+        r: &mut R,
+        sv_list: &[Sv],
+    ) -> Result<Option<BTreeMap<Sv, f64>>, Errors> {
+        let mut bitmask = vec![0_u8; (sv_list.len() + 7) / 8];
+        r.read_exact(&mut bitmask)?;
+        let mut map = BTreeMap::new();
+        for (index, sv) in sv_list.iter().enumerate() {
+            if bitmask[index / 8] & (1 << (index % 8)) != 0 {
+                let mut buf = [0_u8; 8];
+                r.read_exact(&mut buf)?;
+                map.insert(*sv, f64::from_le_bytes(buf));
+            }
+        }
+        Ok(if map.is_empty() { None } else { Some(map) })
+    }
+    /// Serializes this record into a compact MessagePack blob, so it can be
+    /// reloaded an order of magnitude faster than re-parsing the original
+    /// ASCII file for repeated interpolation workloads.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack<W: Write>(&self, w: W) -> Result<(), Errors> {
+        rmp_serde::encode::write(&mut std::io::BufWriter::new(w), self)?;
+        Ok(())
+    }
+    /// Deserializes an [`SP3`] record previously written with [`Self::to_msgpack`].
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack<R: Read>(r: R) -> Result<Self, Errors> {
+        let sp3 = rmp_serde::decode::from_read(r)?;
+        Ok(sp3)
+    }
+}
+
+use merge::{MergeError, MergeStrategy};
+
+impl SP3 {
+    /// Does the actual work of [`Merge::merge_mut_with`], in place. Conflicts
+    /// detected partway through (e.g. in the `clock` loop, under
+    /// [`MergeStrategy::Error`]) leave `self` partially mutated by whichever
+    /// record types were merged first, so callers must only ever run this
+    /// against a disposable clone - see [`Merge::merge_mut_with`].
+    fn merge_mut_with_unchecked(
+        &mut self,
+        rhs: &Self,
+        strategy: MergeStrategy,
+    ) -> Result<(), MergeError> {
         if self.agency != rhs.agency {
             return Err(MergeError::DataProvider);
         }
@@ -694,10 +1859,7 @@ impl Merge for SP3 {
             return Err(MergeError::CoordSystem);
         }
         if self.constellation != rhs.constellation {
-            /*
-             * Convert self to Mixed constellation
-             */
-            self.constellation = Constellation::Mixed;
+            return Err(MergeError::ConstellationMismatch);
         }
         // adjust revision
         if rhs.version > self.version {
@@ -731,11 +1893,25 @@ impl Merge for SP3 {
         for (epoch, svnn) in &rhs.position {
             if let Some(lhs_sv) = self.position.get_mut(epoch) {
                 for (sv, position) in svnn {
-                    lhs_sv.insert(*sv, *position);
+                    match lhs_sv.get(sv) {
+                        Some(existing) if *existing != *position => match strategy {
+                            MergeStrategy::KeepSelf => {}
+                            MergeStrategy::TakeRhs | MergeStrategy::PreferFinal => {
+                                lhs_sv.insert(*sv, *position);
+                            }
+                            MergeStrategy::Error => {
+                                return Err(MergeError::EpochSvConflict(*epoch, *sv));
+                            }
+                        },
+                        _ => {
+                            lhs_sv.insert(*sv, *position);
+                        }
+                    }
                 }
             } else {
                 // introduce new epoch
-                self.epoch.push(*epoch);
+                self.epoch.insert(*epoch);
+                self.stamp_epoch(*epoch);
                 self.position.insert(*epoch, svnn.clone());
             }
         }
@@ -745,22 +1921,27 @@ impl Merge for SP3 {
         for (epoch, svnn) in &rhs.clock {
             if let Some(lhs_sv) = self.clock.get_mut(epoch) {
                 for (sv, clock) in svnn {
-                    lhs_sv.insert(*sv, *clock);
+                    match lhs_sv.get(sv) {
+                        Some(existing) if *existing != *clock => match strategy {
+                            MergeStrategy::KeepSelf => {}
+                            MergeStrategy::TakeRhs | MergeStrategy::PreferFinal => {
+                                lhs_sv.insert(*sv, *clock);
+                            }
+                            MergeStrategy::Error => {
+                                return Err(MergeError::EpochSvConflict(*epoch, *sv));
+                            }
+                        },
+                        _ => {
+                            lhs_sv.insert(*sv, *clock);
+                        }
+                    }
                 }
             } else {
                 // introduce new epoch : in clock record
                 self.clock.insert(*epoch, svnn.clone());
-                // introduce new epoch : if not contained in positions
-                let mut found = false;
-                for e in &self.epoch {
-                    found |= *e == *epoch;
-                    if found {
-                        break;
-                    }
-                }
-                if !found {
-                    self.epoch.push(*epoch);
-                }
+                // O(log n) membership + insertion, no linear scan needed
+                self.epoch.insert(*epoch);
+                self.stamp_epoch(*epoch);
             }
         }
         /*
@@ -769,27 +1950,87 @@ impl Merge for SP3 {
         for (epoch, svnn) in &rhs.velocities {
             if let Some(lhs_sv) = self.velocities.get_mut(epoch) {
                 for (sv, position) in svnn {
-                    lhs_sv.insert(*sv, *position);
+                    match lhs_sv.get(sv) {
+                        Some(existing) if *existing != *position => match strategy {
+                            MergeStrategy::KeepSelf => {}
+                            MergeStrategy::TakeRhs | MergeStrategy::PreferFinal => {
+                                lhs_sv.insert(*sv, *position);
+                            }
+                            MergeStrategy::Error => {
+                                return Err(MergeError::EpochSvConflict(*epoch, *sv));
+                            }
+                        },
+                        _ => {
+                            lhs_sv.insert(*sv, *position);
+                        }
+                    }
                 }
             } else {
                 // introduce new epoch
                 self.velocities.insert(*epoch, svnn.clone());
-                // introduce new epoch : if not contained in positions
-                let mut found = false;
-                for e in &self.epoch {
-                    found |= *e == *epoch;
-                    if found {
-                        break;
+                // O(log n) membership + insertion, no linear scan needed
+                self.epoch.insert(*epoch);
+                self.stamp_epoch(*epoch);
+            }
+        }
+        /*
+         * Merge possible new clock-rate estimates
+         */
+        for (epoch, svnn) in &rhs.clock_rate {
+            if let Some(lhs_sv) = self.clock_rate.get_mut(epoch) {
+                for (sv, rate) in svnn {
+                    match lhs_sv.get(sv) {
+                        Some(existing) if *existing != *rate => match strategy {
+                            MergeStrategy::KeepSelf => {}
+                            MergeStrategy::TakeRhs | MergeStrategy::PreferFinal => {
+                                lhs_sv.insert(*sv, *rate);
+                            }
+                            MergeStrategy::Error => {
+                                return Err(MergeError::EpochSvConflict(*epoch, *sv));
+                            }
+                        },
+                        _ => {
+                            lhs_sv.insert(*sv, *rate);
+                        }
                     }
                 }
-                if !found {
-                    self.epoch.push(*epoch);
-                }
+            } else {
+                // introduce new epoch
+                self.clock_rate.insert(*epoch, svnn.clone());
+                // O(log n) membership + insertion, no linear scan needed
+                self.epoch.insert(*epoch);
+                self.stamp_epoch(*epoch);
             }
         }
+        // merge comment lists, without duplicates
+        for comment in &rhs.comments {
+            if !self.comments.contains(comment) {
+                self.comments.push(comment.clone());
+            }
+        }
+
+        // the BTreeSet keeps epochs intrinsically ordered: no trailing sort needed
+        Ok(())
+    }
+}
 
-        // maintain Epochs in correct order
-        self.epoch.sort();
+impl Merge for SP3 {
+    fn merge(&self, rhs: &Self) -> Result<Self, MergeError> {
+        let mut s = self.clone();
+        s.merge_mut(rhs)?;
+        Ok(s)
+    }
+    fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError> {
+        self.merge_mut_with(rhs, MergeStrategy::default())
+    }
+    fn merge_mut_with(&mut self, rhs: &Self, strategy: MergeStrategy) -> Result<(), MergeError> {
+        // merge into a clone first: a conflict detected partway through (say,
+        // in the clock-rate loop) must not leave `self` with the position
+        // and clock loops already applied from `rhs`, which merge_mut_with's
+        // contract (a rejected merge leaves `self` untouched) requires.
+        let mut merged = self.clone();
+        merged.merge_mut_with_unchecked(rhs, strategy)?;
+        *self = merged;
         Ok(())
     }
 }
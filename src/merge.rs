@@ -1,15 +1,53 @@
 //! SP3 file merging operations.
 
+use hifitime::Epoch;
+use rinex::prelude::Sv;
 use thiserror::Error;
 
 #[derive(Debug, Clone, Error)]
-pub enum MergeError {}
+pub enum MergeError {
+    #[error("data provider (agency) mismatch")]
+    DataProvider,
+    #[error("time scale mismatch")]
+    TimeScale,
+    #[error("coordinates system mismatch")]
+    CoordSystem,
+    #[error("constellation mismatch")]
+    ConstellationMismatch,
+    #[error("conflicting value for {1} @ {0}")]
+    EpochSvConflict(Epoch, Sv),
+}
+
+/// Conflict-resolution policy applied when both merge operands provide a
+/// value for the same (epoch, SV), used by [`Merge::merge_mut_with`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the left-hand side (`self`) value.
+    KeepSelf,
+    /// Take the right-hand side (`rhs`) value.
+    #[default]
+    TakeRhs,
+    /// Prefer whichever operand the caller considers final/higher-precision.
+    /// Callers merge that product in as `rhs`, so this behaves like
+    /// [`Self::TakeRhs`].
+    PreferFinal,
+    /// Reject the merge, returning [`MergeError::EpochSvConflict`] naming
+    /// the offending epoch and SV.
+    Error,
+}
 
 pub trait Merge {
     fn merge(&self, rhs: &Self) -> Result<Self, MergeError>
     where
         Self: Sized;
+    /// Merges `rhs` into `self`, resolving (epoch, SV) collisions with the
+    /// default [`MergeStrategy`].
     fn merge_mut(&mut self, rhs: &Self) -> Result<(), MergeError>
     where
         Self: Sized;
+    /// Merges `rhs` into `self`, resolving (epoch, SV) collisions according
+    /// to the given [`MergeStrategy`].
+    fn merge_mut_with(&mut self, rhs: &Self, strategy: MergeStrategy) -> Result<(), MergeError>
+    where
+        Self: Sized;
 }
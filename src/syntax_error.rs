@@ -0,0 +1,26 @@
+//! Structured SP3 parsing diagnostics
+use std::ops::Range;
+
+/// A single parsing problem encountered while walking an SP3 file.
+/// [`crate::SP3::from_reader`] accumulates every [`SyntaxError`] it runs
+/// into instead of aborting on the first one, so callers can report every
+/// offending line of a malformed product at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    /// Human readable description of the problem.
+    pub message: String,
+    /// 1-based line number the problem was found on.
+    pub line: usize,
+    /// Byte span within that (trimmed) line the problem relates to.
+    pub span: Range<usize>,
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {} (bytes {}..{})",
+            self.line, self.message, self.span.start, self.span.end
+        )
+    }
+}
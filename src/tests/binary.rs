@@ -0,0 +1,69 @@
+//! Binary cache (to_binary/from_binary) round-trip tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    fn two_epoch_sp3() -> SP3 {
+        let e0 = Epoch::from_str("2023-01-01T00:00:00.000000005 UTC").unwrap();
+        let e1 = Epoch::from_str("2023-01-01T00:15:00 UTC").unwrap();
+        let sv = Sv::from_str("G01").unwrap();
+
+        let mut e0_positions = BTreeMap::new();
+        e0_positions.insert(sv, (1.0_f64, 2.0_f64, 3.0_f64));
+        let mut e1_positions = BTreeMap::new();
+        e1_positions.insert(sv, (4.0_f64, 5.0_f64, 6.0_f64));
+
+        let mut position = BTreeMap::new();
+        position.insert(e0, e0_positions);
+        position.insert(e1, e1_positions);
+
+        SP3 {
+            data_type: DataType::Position,
+            epoch: BTreeSet::from([e0, e1]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nanoseconds_survive_the_round_trip() {
+        let sp3 = two_epoch_sp3();
+        let mut buf = Vec::new();
+        sp3.to_binary(&mut buf).unwrap();
+
+        let restored = SP3::from_binary(&mut buf.as_slice(), DataType::Position).unwrap();
+        assert_eq!(restored.epoch, sp3.epoch);
+        assert_eq!(
+            restored.sv_position().collect::<Vec<_>>(),
+            sp3.sv_position().collect::<Vec<_>>(),
+            "bitmask+triplet payload should survive the round trip"
+        );
+    }
+
+    #[test]
+    fn epoch_ordinals_are_repopulated_so_iter_since_still_works() {
+        let sp3 = two_epoch_sp3();
+        let mut buf = Vec::new();
+        sp3.to_binary(&mut buf).unwrap();
+
+        let mut restored = SP3::from_binary(&mut buf.as_slice(), DataType::Position).unwrap();
+
+        // a fresh cursor must surface every restored epoch exactly once
+        let mut cursor = Cursor::default();
+        let first = restored.iter_since(&mut cursor);
+        assert_eq!(first.position.len(), 2, "expected both restored epochs' position entries");
+        assert_eq!(
+            first.position,
+            sp3.sv_position().collect::<Vec<_>>(),
+            "restored position payload should match the original bitmask+triplet encoding"
+        );
+
+        // a second call with the now-advanced cursor must see nothing new
+        let second = restored.iter_since(&mut cursor);
+        assert!(second.position.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Clock interpolation and interpolation_window tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    const BAD_OR_ABSENT: f64 = 999999.999999;
+
+    fn linear_clock_sp3() -> SP3 {
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let dt = Duration::from_seconds(300.0);
+
+        let mut epoch = BTreeSet::new();
+        let mut clock = BTreeMap::new();
+        for i in 0..5 {
+            let e = t0 + Duration::from_seconds(i as f64 * 300.0);
+            epoch.insert(e);
+            let mut sv_clk = BTreeMap::new();
+            sv_clk.insert(sv, 10.0 + i as f64); // linear clock drift, us
+            clock.insert(e, sv_clk);
+        }
+
+        SP3 {
+            data_type: DataType::Position,
+            epoch_interval: dt,
+            epoch,
+            sv: vec![sv],
+            clock,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn clock_interpolates_between_samples() {
+        let sp3 = linear_clock_sp3();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+
+        // the clock values are linear in time (val = 10 + t/300s), so any
+        // window should reproduce that line exactly
+        let query = t0 + Duration::from_seconds(650.0);
+        let interpolated = sp3
+            .sv_clock_interpolate(query, sv, 3)
+            .expect("clock interpolation should be feasible here");
+        assert!((interpolated - (10.0 + 650.0 / 300.0)).abs() < 1.0E-6);
+    }
+
+    #[test]
+    fn clock_interpolation_rejects_bad_or_absent_sentinel() {
+        let mut sp3 = linear_clock_sp3();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+
+        let corrupted_epoch = t0 + Duration::from_seconds(300.0);
+        sp3.clock
+            .get_mut(&corrupted_epoch)
+            .unwrap()
+            .insert(sv, BAD_OR_ABSENT);
+
+        let query = t0 + Duration::from_seconds(650.0);
+        assert!(sp3.sv_clock_interpolate(query, sv, 3).is_none());
+    }
+
+    #[test]
+    fn interpolation_window_excludes_the_edge_margins() {
+        let sp3 = linear_clock_sp3();
+        let first = sp3.first_epoch().unwrap();
+        let last = sp3.last_epoch().unwrap();
+        let dt = sp3.epoch_interval;
+
+        let (win_first, win_last) = sp3
+            .interpolation_window(2)
+            .expect("window should be computable on a non-empty record");
+        assert_eq!(win_first, first + dt);
+        assert_eq!(win_last, last - dt);
+    }
+
+    #[test]
+    fn interpolation_window_is_none_without_epochs() {
+        let sp3 = SP3::default();
+        assert!(sp3.interpolation_window(2).is_none());
+    }
+}
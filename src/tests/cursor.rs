@@ -0,0 +1,62 @@
+//! Cursor/iter_since tests, exercised across Merge::merge_mut as documented
+#[cfg(test)]
+mod test {
+    use crate::merge::Merge;
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    fn single_epoch_sp3(e: Epoch, pos: (f64, f64, f64)) -> SP3 {
+        let sv = Sv::from_str("G01").unwrap();
+        let mut sv_pos = BTreeMap::new();
+        sv_pos.insert(sv, pos);
+        let mut position = BTreeMap::new();
+        position.insert(e, sv_pos);
+
+        SP3 {
+            data_type: DataType::Position,
+            epoch: BTreeSet::from([e]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn iter_since_only_returns_entries_appended_after_the_merge() {
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let t1 = Epoch::from_str("2022-12-31T23:55:00 UTC").unwrap();
+
+        let mut sp3 = single_epoch_sp3(t0, (1.0, 1.0, 1.0));
+        let mut cursor = Cursor::default();
+
+        // draining right after construction consumes the initial epoch
+        let first = sp3.iter_since(&mut cursor);
+        assert_eq!(first.position.len(), 1);
+
+        // merge in a chronologically-earlier epoch, as the doc comment
+        // warns can happen: iter_since must still key off insertion order,
+        // not Epoch ordering, so it must surface exactly this new entry
+        let earlier = single_epoch_sp3(t1, (2.0, 2.0, 2.0));
+        sp3.merge_mut(&earlier).unwrap();
+
+        let second = sp3.iter_since(&mut cursor);
+        assert_eq!(second.position.len(), 1);
+        assert_eq!(second.position[0].0, t1);
+
+        // nothing left to drain
+        let third = sp3.iter_since(&mut cursor);
+        assert!(third.position.is_empty());
+    }
+
+    #[test]
+    fn fresh_cursor_sees_everything_already_present() {
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let mut sp3 = single_epoch_sp3(t0, (1.0, 1.0, 1.0));
+
+        let mut cursor = Cursor::default();
+        let entries = sp3.iter_since(&mut cursor);
+        assert_eq!(entries.position.len(), 1);
+    }
+}
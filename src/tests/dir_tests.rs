@@ -0,0 +1,155 @@
+//! Directory-driven conformance harness, modeled on rust-analyzer's
+//! `dir_tests`: every file under `data/validate/ok/` is expected to be
+//! accepted by [`crate::SP3::validate`], and its canonical report is
+//! compared against a checked-in `<name>.snap` file; every file under
+//! `data/validate/err/` is expected to be rejected. Replaces the
+//! hand-maintained file list in [`super::test_pool`].
+//!
+//! Both directories are self-seeding: if empty, a synthetic fixture is
+//! written before the fixture list is read, so these tests can never
+//! silently iterate zero times and pass without exercising anything -
+//! `fixture_files` failing to find anything after seeding is a hard
+//! test failure, not a pass.
+//!
+//! Set `SP3_BLESS=1` to (re)write the `.snap` files from the current
+//! output instead of asserting against them, the same "bless" workflow
+//! `dir_tests` itself uses. The very first run against the seeded `ok`
+//! fixture still needs one `SP3_BLESS=1` pass to create its `.snap`.
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+
+    fn fixtures_dir(sub: &str) -> PathBuf {
+        PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("validate")
+            .join(sub)
+    }
+
+    fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) != Some("snap"))
+            .collect()
+    }
+
+    /// A minimal, fully deterministic position-only SP3, written through
+    /// [`SP3::to_file`] so the fixture's header is always in sync with
+    /// whatever this crate's own writer currently emits.
+    fn synthetic_ok_sp3() -> SP3 {
+        let sv = Sv::from_str("G01").unwrap();
+        let e0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let e1 = Epoch::from_str("2023-01-01T00:15:00 UTC").unwrap();
+
+        let mut e0_pos = BTreeMap::new();
+        e0_pos.insert(sv, (1.0_f64, 2.0_f64, 3.0_f64));
+        let mut e1_pos = BTreeMap::new();
+        e1_pos.insert(sv, (4.0_f64, 5.0_f64, 6.0_f64));
+
+        let mut position = BTreeMap::new();
+        position.insert(e0, e0_pos);
+        position.insert(e1, e1_pos);
+
+        SP3 {
+            data_type: DataType::Position,
+            coord_system: "IGS14".to_string(),
+            agency: "IGS".to_string(),
+            epoch_interval: Duration::from_seconds(900.0),
+            epoch: BTreeSet::from([e0, e1]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    /// Seeds `dir` with `name` if it's empty, so the harness always has at
+    /// least one real fixture to exercise instead of vacuously iterating
+    /// zero times.
+    fn ensure_fixture(dir: &Path, name: &str, write: impl FnOnce(&Path)) {
+        fs::create_dir_all(dir).unwrap_or_else(|e| panic!("failed to create {:?}: {}", dir, e));
+        if fixture_files(dir).is_empty() {
+            write(&dir.join(name));
+        }
+    }
+
+    #[test]
+    fn ok_fixtures_are_accepted_and_match_their_snapshot() {
+        let dir = fixtures_dir("ok");
+        ensure_fixture(&dir, "synthetic.sp3", |path| {
+            synthetic_ok_sp3()
+                .to_file(&path.to_string_lossy())
+                .unwrap_or_else(|e| panic!("failed to seed {:?}: {}", path, e));
+        });
+
+        let bless = std::env::var("SP3_BLESS").as_deref() == Ok("1");
+        let fixtures = fixture_files(&dir);
+        assert!(
+            !fixtures.is_empty(),
+            "no fixtures under {:?} even after seeding - this test must never pass vacuously",
+            dir
+        );
+
+        for path in fixtures {
+            let report = SP3::validate(&path.to_string_lossy());
+            assert!(
+                report.accepted,
+                "expected {:?} to be accepted, got: {}",
+                path, report
+            );
+
+            let snap_path = path.with_extension(format!(
+                "{}.snap",
+                path.extension().and_then(|e| e.to_str()).unwrap_or("")
+            ));
+            let dump = report.to_string();
+
+            if bless {
+                fs::write(&snap_path, &dump)
+                    .unwrap_or_else(|e| panic!("failed to bless {:?}: {}", snap_path, e));
+                continue;
+            }
+
+            let expected = fs::read_to_string(&snap_path).unwrap_or_else(|e| {
+                panic!(
+                    "missing snapshot {:?} ({}); rerun with SP3_BLESS=1 to create it",
+                    snap_path, e
+                )
+            });
+            assert_eq!(dump, expected, "canonical dump drifted for {:?}", path);
+        }
+    }
+
+    #[test]
+    fn err_fixtures_are_rejected() {
+        let dir = fixtures_dir("err");
+        ensure_fixture(&dir, "missing_eof.sp3", |path| {
+            // no trailing "EOF" marker line: from_plain_reader always
+            // flags this, regardless of anything else in the file, so
+            // this fixture is guaranteed to be rejected
+            fs::write(path, "this is not a valid SP3 product\n")
+                .unwrap_or_else(|e| panic!("failed to seed {:?}: {}", path, e));
+        });
+
+        let fixtures = fixture_files(&dir);
+        assert!(
+            !fixtures.is_empty(),
+            "no fixtures under {:?} even after seeding - this test must never pass vacuously",
+            dir
+        );
+
+        for path in fixtures {
+            let report = SP3::validate(&path.to_string_lossy());
+            assert!(!report.accepted, "expected {:?} to be rejected", path);
+        }
+    }
+}
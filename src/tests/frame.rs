@@ -0,0 +1,103 @@
+//! ECEF/ECI frame conversion tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    fn single_epoch_sp3(e: Epoch, pos: (f64, f64, f64)) -> SP3 {
+        let sv = Sv::from_str("G01").unwrap();
+        let mut sv_pos = BTreeMap::new();
+        sv_pos.insert(sv, pos);
+        let mut position = BTreeMap::new();
+        position.insert(e, sv_pos);
+
+        SP3 {
+            data_type: DataType::Position,
+            epoch: BTreeSet::from([e]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn eci_matches_ecef_at_the_reference_epoch() {
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let sv = Sv::from_str("G01").unwrap();
+        let sp3 = single_epoch_sp3(t0, (1000.0, 2000.0, 3000.0));
+
+        // at the reference epoch itself, the Earth rotation angle is zero,
+        // so ECI must equal ECEF exactly
+        let eci = sp3.sv_position_eci(t0, sv).unwrap();
+        assert_eq!(eci, (1000.0, 2000.0, 3000.0));
+    }
+
+    #[test]
+    fn eci_rotates_about_z_and_preserves_the_z_component() {
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let t1 = t0 + Duration::from_seconds(3600.0);
+        let sv = Sv::from_str("G01").unwrap();
+
+        let mut sv_pos_t0 = BTreeMap::new();
+        sv_pos_t0.insert(sv, (6378.0, 0.0, 0.0));
+        let mut sv_pos_t1 = BTreeMap::new();
+        sv_pos_t1.insert(sv, (6378.0, 0.0, 500.0));
+
+        let mut position = BTreeMap::new();
+        position.insert(t0, sv_pos_t0);
+        position.insert(t1, sv_pos_t1);
+
+        let sp3 = SP3 {
+            data_type: DataType::Position,
+            epoch: BTreeSet::from([t0, t1]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        };
+
+        let eci = sp3.sv_position_eci(t1, sv).unwrap();
+
+        // pure Z rotation must leave the norm of the (x, y) plane unchanged
+        // and leave the z component untouched
+        let xy_norm = (eci.0 * eci.0 + eci.1 * eci.1).sqrt();
+        assert!((xy_norm - 6378.0).abs() < 1.0E-9);
+        assert_eq!(eci.2, 500.0);
+        // the rotation angle after 1h is non-zero, so x must have moved
+        assert!((eci.0 - 6378.0).abs() > 1.0E-6);
+    }
+
+    #[test]
+    fn interpolate_in_frame_ecef_matches_plain_interpolate() {
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let dt = Duration::from_seconds(300.0);
+        let sv = Sv::from_str("G01").unwrap();
+
+        let mut position = BTreeMap::new();
+        for i in 0..3 {
+            let e = t0 + Duration::from_seconds(i as f64 * 300.0);
+            let mut sv_pos = BTreeMap::new();
+            sv_pos.insert(sv, (i as f64, i as f64, i as f64));
+            position.insert(e, sv_pos);
+        }
+
+        let sp3 = SP3 {
+            data_type: DataType::Position,
+            epoch_interval: dt,
+            epoch: BTreeSet::from([
+                t0,
+                t0 + Duration::from_seconds(300.0),
+                t0 + Duration::from_seconds(600.0),
+            ]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        };
+
+        let query = t0 + Duration::from_seconds(300.0);
+        let plain = sp3.interpolate(query, sv, 1).unwrap();
+        let ecef = sp3.interpolate_in_frame(query, sv, 1, Frame::Ecef).unwrap();
+        assert_eq!(plain, ecef);
+    }
+}
@@ -0,0 +1,159 @@
+//! Hermite interpolation tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::velocity::VelocityRecord;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    // cubic p(t) = 1.0 + 2.0e-2*t + 3.0e-5*t^2 - 4.0e-8*t^3 (km, t in seconds
+    // since t0), with its exact analytic derivative for velocity
+    fn position_km(t: f64) -> f64 {
+        1.0 + 2.0e-2 * t + 3.0e-5 * t * t - 4.0e-8 * t * t * t
+    }
+    fn velocity_km_s(t: f64) -> f64 {
+        2.0e-2 + 6.0e-5 * t - 1.2e-7 * t * t
+    }
+
+    fn cubic_motion_sp3() -> SP3 {
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let dt = 300.0_f64; // 5' sampling, typical of SP3 products
+
+        let mut epoch = BTreeSet::new();
+        let mut position = BTreeMap::new();
+        let mut velocities = VelocityRecord::default();
+        for i in 0..5 {
+            let t = i as f64 * dt;
+            let e = t0 + Duration::from_seconds(t);
+            epoch.insert(e);
+
+            let p = position_km(t);
+            let mut pos_map = BTreeMap::new();
+            pos_map.insert(sv, (p, p, p));
+            position.insert(e, pos_map);
+
+            // velocities field unit is 10^-1 m/s == 1E-4 km/s, so divide the
+            // true km/s value by 1E-4 to get the raw SP3 unit back
+            let v = velocity_km_s(t) / 1.0E-4;
+            let mut vel_map = BTreeMap::new();
+            vel_map.insert(sv, (v, v, v));
+            velocities.insert(e, vel_map);
+        }
+
+        SP3 {
+            data_type: DataType::Velocity,
+            epoch_interval: Duration::from_seconds(dt),
+            epoch,
+            sv: vec![sv],
+            position,
+            velocities,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hermite_matches_analytic_cubic_between_samples() {
+        let sp3 = cubic_motion_sp3();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+
+        // between the samples at t=600s and t=900s
+        let t = 650.0_f64;
+        let epoch = t0 + Duration::from_seconds(t);
+
+        let interpolated = sp3
+            .interpolate_hermite(epoch, sv, 3)
+            .expect("hermite interpolation should be feasible here");
+        let expected = position_km(t);
+
+        assert!(
+            (interpolated.0 - expected).abs() < 1.0E-9,
+            "x: got {} expected {}",
+            interpolated.0,
+            expected
+        );
+        assert!(
+            (interpolated.1 - expected).abs() < 1.0E-9,
+            "y: got {} expected {}",
+            interpolated.1,
+            expected
+        );
+        assert!(
+            (interpolated.2 - expected).abs() < 1.0E-9,
+            "z: got {} expected {}",
+            interpolated.2,
+            expected
+        );
+    }
+
+    #[test]
+    fn hermite_velocity_matches_analytic_derivative() {
+        let sp3 = cubic_motion_sp3();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+
+        let t = 650.0_f64;
+        let epoch = t0 + Duration::from_seconds(t);
+
+        let (_, velocity) = sp3
+            .sv_position_interpolate_hermite(epoch, sv, 3)
+            .expect("hermite interpolation should be feasible here");
+        let expected = velocity_km_s(t);
+
+        assert!(
+            (velocity.0 - expected).abs() < 1.0E-9,
+            "x: got {} expected {}",
+            velocity.0,
+            expected
+        );
+    }
+
+    #[test]
+    fn hermite_requires_velocity_samples() {
+        let mut sp3 = cubic_motion_sp3();
+        sp3.velocities.clear();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+        let epoch = t0 + Duration::from_seconds(650.0);
+
+        assert!(sp3.interpolate_hermite(epoch, sv, 3).is_none());
+    }
+
+    #[test]
+    fn hermite_rejects_bad_or_absent_position_sentinel() {
+        const BAD_OR_ABSENT: f64 = 999999.999999;
+
+        let mut sp3 = cubic_motion_sp3();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+
+        let corrupted_epoch = t0 + Duration::from_seconds(300.0);
+        sp3.position
+            .get_mut(&corrupted_epoch)
+            .unwrap()
+            .insert(sv, (BAD_OR_ABSENT, BAD_OR_ABSENT, BAD_OR_ABSENT));
+
+        let epoch = t0 + Duration::from_seconds(650.0);
+        assert!(sp3.interpolate_hermite(epoch, sv, 3).is_none());
+    }
+
+    #[test]
+    fn hermite_rejects_bad_or_absent_velocity_sentinel() {
+        const BAD_OR_ABSENT: f64 = 999999.999999;
+
+        let mut sp3 = cubic_motion_sp3();
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = sp3.first_epoch().unwrap();
+
+        let corrupted_epoch = t0 + Duration::from_seconds(300.0);
+        sp3.velocities
+            .get_mut(&corrupted_epoch)
+            .unwrap()
+            .insert(sv, (BAD_OR_ABSENT, BAD_OR_ABSENT, BAD_OR_ABSENT));
+
+        let epoch = t0 + Duration::from_seconds(650.0);
+        assert!(sp3.interpolate_hermite(epoch, sv, 3).is_none());
+    }
+}
@@ -0,0 +1,36 @@
+//! SP3 merging tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::merge::{Merge, MergeError};
+    use rinex::prelude::Constellation;
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+
+    fn minimal(constellation: Constellation) -> SP3 {
+        let epoch = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        SP3 {
+            constellation,
+            epoch: BTreeSet::from([epoch]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rejects_constellation_mismatch() {
+        let mut gps = minimal(Constellation::GPS);
+        let glonass = minimal(Constellation::Glonass);
+
+        match gps.merge_mut(&glonass) {
+            Err(MergeError::ConstellationMismatch) => {}
+            other => panic!("expected MergeError::ConstellationMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_matching_constellation() {
+        let mut lhs = minimal(Constellation::GPS);
+        let rhs = minimal(Constellation::GPS);
+        lhs.merge_mut(&rhs).expect("same-constellation merge should succeed");
+    }
+}
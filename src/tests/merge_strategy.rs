@@ -0,0 +1,96 @@
+//! MergeStrategy conflict-resolution tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::merge::MergeError;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    fn base(position_km: f64) -> SP3 {
+        let e = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let sv = Sv::from_str("G01").unwrap();
+
+        let mut positions = BTreeMap::new();
+        positions.insert(sv, (position_km, position_km, position_km));
+        let mut position = BTreeMap::new();
+        position.insert(e, positions);
+
+        SP3 {
+            data_type: DataType::Position,
+            epoch: BTreeSet::from([e]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    fn conflicting_position(sp3: &SP3) -> f64 {
+        let sv = Sv::from_str("G01").unwrap();
+        let e = *sp3.epoch.iter().next().unwrap();
+        sp3.position.get(&e).unwrap().get(&sv).unwrap().0
+    }
+
+    #[test]
+    fn keep_self_ignores_rhs_on_conflict() {
+        let mut lhs = base(1.0);
+        let rhs = base(2.0);
+        lhs.merge_mut_with(&rhs, MergeStrategy::KeepSelf).unwrap();
+        assert_eq!(conflicting_position(&lhs), 1.0);
+    }
+
+    #[test]
+    fn take_rhs_overwrites_on_conflict() {
+        let mut lhs = base(1.0);
+        let rhs = base(2.0);
+        lhs.merge_mut_with(&rhs, MergeStrategy::TakeRhs).unwrap();
+        assert_eq!(conflicting_position(&lhs), 2.0);
+    }
+
+    #[test]
+    fn error_strategy_rejects_conflicting_merge() {
+        let mut lhs = base(1.0);
+        let rhs = base(2.0);
+        let result = lhs.merge_mut_with(&rhs, MergeStrategy::Error);
+        assert!(matches!(result, Err(MergeError::EpochSvConflict(_, _))));
+        // must not have applied any partial change
+        assert_eq!(conflicting_position(&lhs), 1.0);
+    }
+
+    #[test]
+    fn default_strategy_is_take_rhs() {
+        assert_eq!(MergeStrategy::default(), MergeStrategy::TakeRhs);
+    }
+
+    #[test]
+    fn error_strategy_rolls_back_earlier_loops_on_a_later_conflict() {
+        // position agrees between lhs/rhs (so that loop runs clean), but the
+        // clock estimate at the same epoch/Sv conflicts: the clock loop runs
+        // after the position loop, so this only catches a partial-mutation
+        // bug if the rejected merge also leaves lhs.version untouched
+        let e = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let sv = Sv::from_str("G01").unwrap();
+
+        let mut lhs = base(1.0);
+        lhs.version = Version::B;
+        let mut lhs_clock = BTreeMap::new();
+        lhs_clock.insert(sv, 1.0);
+        lhs.clock.insert(e, lhs_clock);
+
+        let mut rhs = base(1.0);
+        rhs.version = Version::D;
+        let mut rhs_clock = BTreeMap::new();
+        rhs_clock.insert(sv, 99.0);
+        rhs.clock.insert(e, rhs_clock);
+
+        let result = lhs.merge_mut_with(&rhs, MergeStrategy::Error);
+        assert!(matches!(result, Err(MergeError::EpochSvConflict(_, _))));
+
+        // the clock conflict is only detected after position and version
+        // have already been merged in-place by the old implementation;
+        // a rejected merge must leave all of them untouched
+        assert_eq!(lhs.version, Version::B);
+        assert_eq!(conflicting_position(&lhs), 1.0);
+        assert_eq!(lhs.clock.get(&e).and_then(|clk| clk.get(&sv)), Some(&1.0));
+    }
+}
@@ -1,8 +1,21 @@
+mod binary;
+mod clock_interpolation;
+mod cursor;
+mod dir_tests;
+mod frame;
+mod hermite;
 mod interpolation;
 mod merge;
+mod merge_strategy;
+mod msgpack;
+mod parallel;
 mod parser_3c;
 mod parser_3d;
+mod slicing;
+mod syntax_errors;
 mod test_pool;
+mod version;
+mod writer;
 
 use crate::SP3;
 
@@ -0,0 +1,55 @@
+//! MessagePack cache (to_msgpack/from_msgpack) round-trip tests
+#[cfg(all(test, feature = "msgpack"))]
+mod test {
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    fn two_epoch_sp3() -> SP3 {
+        let e0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let e1 = Epoch::from_str("2023-01-01T00:15:00 UTC").unwrap();
+        let sv = Sv::from_str("G01").unwrap();
+
+        let mut e0_positions = BTreeMap::new();
+        e0_positions.insert(sv, (1.0_f64, 2.0_f64, 3.0_f64));
+        let mut e1_positions = BTreeMap::new();
+        e1_positions.insert(sv, (4.0_f64, 5.0_f64, 6.0_f64));
+
+        let mut position = BTreeMap::new();
+        position.insert(e0, e0_positions);
+        position.insert(e1, e1_positions);
+
+        SP3 {
+            data_type: DataType::Position,
+            agency: "ESA".to_string(),
+            epoch: BTreeSet::from([e0, e1]),
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn msgpack_round_trip_preserves_records() {
+        let sp3 = two_epoch_sp3();
+        let mut buf = Vec::new();
+        sp3.to_msgpack(&mut buf).expect("failed to encode msgpack");
+
+        let restored = SP3::from_msgpack(buf.as_slice()).expect("failed to decode msgpack");
+
+        assert_eq!(restored.agency, sp3.agency);
+        assert_eq!(restored.epoch, sp3.epoch);
+        assert_eq!(restored.sv, sp3.sv);
+        assert_eq!(
+            restored.sv_position().collect::<Vec<_>>(),
+            sp3.sv_position().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn from_msgpack_rejects_garbage() {
+        let garbage = [0xff_u8; 16];
+        assert!(SP3::from_msgpack(&garbage[..]).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Parity between the sequential and rayon-parallel parsers
+#[cfg(all(test, feature = "rayon"))]
+mod test {
+    use crate::prelude::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn from_file_parallel_matches_from_file() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("COD0MGXFIN_20230500000_01D_05M_ORB.SP3");
+        let path = path.to_string_lossy();
+
+        let sequential = SP3::from_file(&path).expect("sequential parse should succeed");
+        let parallel = SP3::from_file_parallel(&path).expect("parallel parse should succeed");
+
+        assert_eq!(sequential.version, parallel.version);
+        assert_eq!(sequential.data_type, parallel.data_type);
+        assert_eq!(sequential.agency, parallel.agency);
+        assert_eq!(sequential.constellation, parallel.constellation);
+        assert_eq!(sequential.time_system, parallel.time_system);
+        assert_eq!(sequential.epoch, parallel.epoch);
+        assert_eq!(sequential.sv, parallel.sv);
+        assert_eq!(sequential.position, parallel.position);
+        assert_eq!(sequential.clock, parallel.clock);
+        assert_eq!(sequential.velocities, parallel.velocities);
+        assert_eq!(sequential.clock_rate, parallel.clock_rate);
+    }
+}
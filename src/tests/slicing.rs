@@ -0,0 +1,77 @@
+//! Epoch-range slicing, decimation and resampling tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::str::FromStr;
+
+    const STEP_SECONDS: f64 = 300.0;
+
+    fn five_epoch_sp3() -> SP3 {
+        let sv = Sv::from_str("G01").unwrap();
+        let t0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let dt = Duration::from_seconds(STEP_SECONDS);
+
+        let mut epoch = BTreeSet::new();
+        let mut position = BTreeMap::new();
+        for i in 0..5 {
+            let e = t0 + Duration::from_seconds(i as f64 * STEP_SECONDS);
+            epoch.insert(e);
+            let mut sv_pos = BTreeMap::new();
+            sv_pos.insert(sv, (i as f64, i as f64, i as f64));
+            position.insert(e, sv_pos);
+        }
+
+        SP3 {
+            data_type: DataType::Position,
+            epoch_interval: dt,
+            epoch,
+            sv: vec![sv],
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn slice_keeps_only_the_requested_window() {
+        let sp3 = five_epoch_sp3();
+        let t0 = sp3.first_epoch().unwrap();
+        let start = t0 + Duration::from_seconds(STEP_SECONDS);
+        let end = t0 + Duration::from_seconds(3.0 * STEP_SECONDS);
+
+        let sliced = sp3.slice(start, end);
+        assert_eq!(sliced.epoch.len(), 3);
+        assert!(sliced.epoch.iter().all(|e| *e >= start && *e <= end));
+        assert_eq!(sliced.position.len(), 3);
+    }
+
+    #[test]
+    fn decimate_by_interval_drops_samples_closer_than_dt() {
+        let sp3 = five_epoch_sp3();
+        let target_dt = Duration::from_seconds(2.0 * STEP_SECONDS);
+
+        let decimated = sp3.decimate_by_interval(target_dt);
+        assert_eq!(decimated.epoch.len(), 3); // epochs 0, 2, 4
+        assert_eq!(decimated.epoch_interval, target_dt);
+    }
+
+    #[test]
+    fn decimate_by_ratio_keeps_every_rth_epoch() {
+        let sp3 = five_epoch_sp3();
+
+        let decimated = sp3.decimate_by_ratio(2);
+        assert_eq!(decimated.epoch.len(), 3); // epochs 0, 2, 4
+        assert_eq!(
+            decimated.epoch_interval,
+            Duration::from_seconds(2.0 * STEP_SECONDS)
+        );
+    }
+
+    #[test]
+    fn decimate_by_ratio_one_is_a_no_op() {
+        let sp3 = five_epoch_sp3();
+        let decimated = sp3.decimate_by_ratio(1);
+        assert_eq!(decimated.epoch, sp3.epoch);
+    }
+}
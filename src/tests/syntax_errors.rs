@@ -0,0 +1,68 @@
+//! SyntaxError collection tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::Errors;
+    use std::collections::BTreeSet;
+    use std::io::{BufReader, Cursor};
+    use std::str::FromStr;
+
+    fn minimal_sp3() -> SP3 {
+        let epoch = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        SP3 {
+            epoch: BTreeSet::from([epoch]),
+            ..Default::default()
+        }
+    }
+
+    fn syntax_errors(content: &str) -> Vec<SyntaxError> {
+        match SP3::from_reader(BufReader::new(Cursor::new(content.to_string()))) {
+            Err(Errors::Syntax(errors)) => errors,
+            other => panic!("expected Errors::Syntax, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_missing_trailing_eof() {
+        let mut content = Vec::new();
+        minimal_sp3().to_writer(&mut content).unwrap();
+        let content = String::from_utf8(content).unwrap();
+        // `to_writer` ends with a trailing "EOF" marker and no newline before it
+        let without_eof = content.strip_suffix("EOF").unwrap();
+
+        let errors = syntax_errors(without_eof);
+        assert_eq!(errors.len(), 1, "unexpected errors: {:?}", errors);
+        assert!(errors[0].message.contains("EOF"));
+    }
+
+    #[test]
+    fn collects_every_error_instead_of_bailing_on_the_first() {
+        let mut content = Vec::new();
+        minimal_sp3().to_writer(&mut content).unwrap();
+        let content = String::from_utf8(content).unwrap();
+        let content = content.strip_suffix("EOF").unwrap();
+
+        // corrupt the epoch line's year field so parse_epoch fails, while
+        // also leaving the trailing EOF marker stripped, so this single
+        // parse hits two independent, unrelated errors
+        let corrupted: Vec<String> = content
+            .lines()
+            .map(|line| {
+                if let Some(rest) = line.strip_prefix("*  ") {
+                    format!("*  X{}", &rest[1..])
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+        let corrupted = corrupted.join("\n");
+
+        let errors = syntax_errors(&corrupted);
+        assert!(
+            errors.len() >= 2,
+            "expected at least 2 collected errors, got {:?}",
+            errors
+        );
+        assert!(errors.iter().any(|e| e.message.contains("EOF")));
+    }
+}
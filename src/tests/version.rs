@@ -0,0 +1,36 @@
+//! Version-dependent parsing behavior
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::Errors;
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+
+    #[test]
+    fn sp3_a_rejects_velocity_records() {
+        let epoch = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let sp3 = SP3 {
+            version: Version::A,
+            data_type: DataType::Velocity,
+            epoch: BTreeSet::from([epoch]),
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        sp3.to_writer(&mut buf)
+            .expect("failed to serialize synthetic SP3-a/velocity fixture");
+
+        match SP3::from_reader(buf.as_slice()) {
+            Err(Errors::Syntax(errors)) => {
+                assert!(
+                    errors
+                        .iter()
+                        .any(|e| e.message.contains("SP3-a does not support velocity")),
+                    "expected a SyntaxError flagging SP3-a/velocity incompatibility, got {:?}",
+                    errors
+                );
+            }
+            other => panic!("expected Errors::Syntax, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! writer dedicated tests
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::tests::test_equality;
+    use crate::velocity::VelocityRecord;
+    use rinex::prelude::Sv;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    #[test]
+    fn clock_and_clock_rate_round_trip() {
+        // the writer only emits a clock/clock-rate value alongside a
+        // position/velocity sample for the same (epoch, Sv), so this needs
+        // both populated to actually exercise the clock-rate line
+        let sv = Sv::from_str("G01").unwrap();
+        let e0 = Epoch::from_str("2023-01-01T00:00:00 UTC").unwrap();
+        let e1 = Epoch::from_str("2023-01-01T00:15:00 UTC").unwrap();
+
+        let mut position = BTreeMap::new();
+        let mut e0_pos = BTreeMap::new();
+        e0_pos.insert(sv, (1.0_f64, 2.0_f64, 3.0_f64));
+        position.insert(e0, e0_pos);
+        let mut e1_pos = BTreeMap::new();
+        e1_pos.insert(sv, (4.0_f64, 5.0_f64, 6.0_f64));
+        position.insert(e1, e1_pos);
+
+        let mut velocities = VelocityRecord::default();
+        let mut e0_vel = BTreeMap::new();
+        e0_vel.insert(sv, (0.1_f64, 0.2_f64, 0.3_f64));
+        velocities.insert(e0, e0_vel);
+        let mut e1_vel = BTreeMap::new();
+        e1_vel.insert(sv, (0.4_f64, 0.5_f64, 0.6_f64));
+        velocities.insert(e1, e1_vel);
+
+        let mut clock = BTreeMap::new();
+        let mut e0_clk = BTreeMap::new();
+        e0_clk.insert(sv, 123.456_f64);
+        clock.insert(e0, e0_clk);
+        let mut e1_clk = BTreeMap::new();
+        e1_clk.insert(sv, 124.0_f64);
+        clock.insert(e1, e1_clk);
+
+        let mut clock_rate = BTreeMap::new();
+        let mut e0_rate = BTreeMap::new();
+        e0_rate.insert(sv, 0.5_f64);
+        clock_rate.insert(e0, e0_rate);
+        let mut e1_rate = BTreeMap::new();
+        e1_rate.insert(sv, 0.6_f64);
+        clock_rate.insert(e1, e1_rate);
+
+        let sp3 = SP3 {
+            data_type: DataType::Velocity,
+            epoch: BTreeSet::from([e0, e1]),
+            sv: vec![sv],
+            position,
+            velocities,
+            clock,
+            clock_rate,
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        sp3.to_writer(&mut buf).expect("failed to write SP3");
+
+        let parsed = SP3::from_reader(buf.as_slice());
+        assert!(
+            parsed.is_ok(),
+            "failed to re-parse generated clock/clock-rate SP3: {:?}",
+            parsed.err()
+        );
+
+        test_equality(sp3, parsed.unwrap(), true);
+    }
+
+    #[test]
+    fn velocity_round_trip() {
+        let path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("example_velocity.sp3");
+        let sp3 = SP3::from_file(&path.to_string_lossy());
+        assert!(
+            sp3.is_ok(),
+            "failed to parse data/example_velocity.sp3: {:?}",
+            sp3.err()
+        );
+        let sp3 = sp3.unwrap();
+        assert_eq!(sp3.data_type, DataType::Velocity);
+
+        let copy_path = PathBuf::new()
+            .join(env!("CARGO_MANIFEST_DIR"))
+            .join("data")
+            .join("example_velocity.sp3.copy");
+        sp3.to_file(&copy_path.to_string_lossy())
+            .expect("failed to write SP3 copy");
+
+        let parsed = SP3::from_file(&copy_path.to_string_lossy());
+        assert!(
+            parsed.is_ok(),
+            "failed to re-parse generated copy: {:?}",
+            parsed.err()
+        );
+
+        test_equality(sp3, parsed.unwrap(), true);
+    }
+}
@@ -0,0 +1,133 @@
+//! SP3 product conformance screening
+use crate::{DataType, SP3};
+use hifitime::{Duration, Epoch, TimeScale};
+
+/// Outcome of [`SP3::validate`]: a programmatic summary of which SP3 header
+/// constraints a product satisfies, so downstream users can screen a file
+/// before trusting it, without re-deriving these checks themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    /// Whether the file was accepted. Every file that parses is accepted:
+    /// the fields below are reported, none of them causes a rejection.
+    pub accepted: bool,
+    /// Set when [`Self::accepted`] is false: why the file was rejected.
+    pub rejection_reason: Option<String>,
+    /// Producing agency, as found in the header.
+    pub agency: String,
+    /// Coordinate system, as found in the header.
+    pub coord_system: String,
+    /// Time system the epochs are expressed in.
+    pub time_system: TimeScale,
+    /// Declared data type (position or velocity); clock content is
+    /// reported separately by [`Self::has_clock`].
+    pub data_type: DataType,
+    /// Number of distinct Epochs found.
+    pub nb_epochs: usize,
+    /// Number of distinct satellite vehicles found.
+    pub nb_sv: usize,
+    /// Identifiers of every satellite vehicle found, sorted.
+    pub satellites: Vec<String>,
+    /// Declared sampling interval, in seconds.
+    pub sampling_interval_seconds: f64,
+    /// Whether any position sample was found.
+    pub has_position: bool,
+    /// Whether any clock offset sample was found.
+    pub has_clock: bool,
+    /// Whether any velocity sample was found.
+    pub has_velocity: bool,
+    /// Whether any clock-rate sample was found.
+    pub has_clock_rate: bool,
+    /// Epoch pairs `(before, after)` where the gap between two consecutive
+    /// Epochs exceeds the declared `epoch_interval`.
+    pub gaps: Vec<(Epoch, Epoch)>,
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if !self.accepted {
+            return write!(
+                f,
+                "rejected: {}",
+                self.rejection_reason.as_deref().unwrap_or("unknown error")
+            );
+        }
+        writeln!(f, "agency: {}", self.agency)?;
+        writeln!(f, "coordinates system: {}", self.coord_system)?;
+        writeln!(f, "time system: {}", self.time_system)?;
+        writeln!(f, "data type: {}", self.data_type)?;
+        writeln!(f, "epochs: {}", self.nb_epochs)?;
+        writeln!(f, "sampling interval: {}s", self.sampling_interval_seconds)?;
+        writeln!(f, "satellite vehicles ({}): {}", self.nb_sv, self.satellites.join(","))?;
+        writeln!(
+            f,
+            "records: position={} clock={} velocity={} clock_rate={}",
+            self.has_position, self.has_clock, self.has_velocity, self.has_clock_rate
+        )?;
+        write!(f, "gaps: {}", self.gaps.len())
+    }
+}
+
+impl SP3 {
+    /// Parses `path` and summarizes the resulting [`SP3`] (producing agency,
+    /// coordinate system, declared data type, sampling gaps), returning a
+    /// [`ValidationReport`] either way so callers can decide whether to
+    /// trust the product without having to catch a parsing [`Errors`]
+    /// themselves. Only a parsing failure rejects the file; the summarized
+    /// properties are not checked against any expected value.
+    pub fn validate(path: &str) -> ValidationReport {
+        match Self::from_file(path) {
+            Ok(sp3) => sp3.validation_report(),
+            Err(e) => ValidationReport {
+                accepted: false,
+                rejection_reason: Some(e.to_string()),
+                agency: String::new(),
+                coord_system: String::new(),
+                time_system: TimeScale::default(),
+                data_type: DataType::default(),
+                nb_epochs: 0,
+                nb_sv: 0,
+                satellites: Vec::new(),
+                sampling_interval_seconds: 0.0,
+                has_position: false,
+                has_clock: false,
+                has_velocity: false,
+                has_clock_rate: false,
+                gaps: Vec::new(),
+            },
+        }
+    }
+    fn validation_report(&self) -> ValidationReport {
+        let mut gaps = Vec::new();
+        let mut previous: Option<Epoch> = None;
+        for epoch in self.epoch.iter() {
+            if let Some(prev) = previous {
+                let dt = *epoch - prev;
+                if self.epoch_interval > Duration::default() && dt > self.epoch_interval {
+                    gaps.push((prev, *epoch));
+                }
+            }
+            previous = Some(*epoch);
+        }
+
+        let mut satellites: Vec<String> = self.sv().map(|sv| sv.to_string()).collect();
+        satellites.sort();
+
+        ValidationReport {
+            accepted: true,
+            rejection_reason: None,
+            agency: self.agency.clone(),
+            coord_system: self.coord_system.clone(),
+            time_system: self.time_system,
+            data_type: self.data_type,
+            nb_epochs: self.epoch.len(),
+            nb_sv: self.sv.len(),
+            satellites,
+            sampling_interval_seconds: self.epoch_interval.to_seconds(),
+            has_position: !self.position.is_empty(),
+            has_clock: !self.clock.is_empty(),
+            has_velocity: !self.velocities.is_empty(),
+            has_clock_rate: !self.clock_rate.is_empty(),
+            gaps,
+        }
+    }
+}
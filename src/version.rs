@@ -4,6 +4,8 @@ use crate::Errors;
 
 #[derive(Default, Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Hash)]
 pub enum Version {
+    A,
+    B,
     C,
     #[default]
     D,
@@ -12,6 +14,8 @@ pub enum Version {
 impl std::fmt::Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            Self::A => f.write_str("a"),
+            Self::B => f.write_str("b"),
             Self::C => f.write_str("c"),
             Self::D => f.write_str("d"),
         }
@@ -25,6 +29,10 @@ impl std::str::FromStr for Version {
             Ok(Self::D)
         } else if s.eq("c") {
             Ok(Self::C)
+        } else if s.eq("b") {
+            Ok(Self::B)
+        } else if s.eq("a") {
+            Ok(Self::A)
         } else {
             Err(Errors::UnknownVersion(s.to_string()))
         }
@@ -34,8 +42,10 @@ impl std::str::FromStr for Version {
 impl From<Version> for u8 {
     fn from(val: Version) -> Self {
         match val {
-            Version::D => 4,
+            Version::A => 1,
+            Version::B => 2,
             Version::C => 3,
+            Version::D => 4,
         }
     }
 }
@@ -44,7 +54,9 @@ impl From<u8> for Version {
     fn from(lhs: u8) -> Version {
         match lhs {
             4..=u8::MAX => Version::D,
-            0..=3 => Version::C,
+            3 => Version::C,
+            2 => Version::B,
+            0..=1 => Version::A,
         }
     }
 }
@@ -71,19 +83,32 @@ mod test {
     use std::str::FromStr;
     #[test]
     fn version() {
-        for (desc, expected) in vec![("c", Version::C), ("d", Version::D)] {
-            assert!(
-                Version::from_str(desc).is_ok(),
+        for (desc, expected) in vec![
+            ("a", Version::A),
+            ("b", Version::B),
+            ("c", Version::C),
+            ("d", Version::D),
+        ] {
+            assert_eq!(
+                Version::from_str(desc),
+                Ok(expected),
                 "failed to parse Version from \"{}\"",
                 desc
             );
         }
 
-        for (vers, expected) in vec![(Version::C, 3), (Version::D, 4)] {
+        for (vers, expected) in vec![
+            (Version::A, 1),
+            (Version::B, 2),
+            (Version::C, 3),
+            (Version::D, 4),
+        ] {
             let version: u8 = vers.into();
             assert_eq!(version, expected, "convertion to integer failed");
         }
 
+        assert!(Version::A < Version::B);
+        assert!(Version::B < Version::C);
         assert!(Version::C < Version::D);
         assert!(Version::D >= Version::C);
 
@@ -95,6 +120,12 @@ mod test {
         let version: Version = 3_u8.into();
         assert_eq!(version, Version::C);
         assert_eq!(version + 1, Version::D);
-        assert_eq!(version - 1, Version::C);
+        assert_eq!(version - 1, Version::B);
+
+        let version: Version = 2_u8.into();
+        assert_eq!(version, Version::B);
+
+        let version: Version = 1_u8.into();
+        assert_eq!(version, Version::A);
     }
 }
@@ -2,20 +2,28 @@
 use std::fs::File;
 use std::io::{BufWriter, IoSlice, Write};
 
-pub(crate) struct BufferedWriter {
-    buf: BufWriter<File>,
+pub(crate) struct BufferedWriter<W: Write> {
+    buf: BufWriter<W>,
 }
 
-impl BufferedWriter {
+impl BufferedWriter<File> {
     pub fn new(path: &str) -> std::io::Result<Self> {
         let fd = File::create(path)?;
-        Ok(Self {
-            buf: BufWriter::new(fd),
-        })
+        Ok(Self::from_writer(fd))
     }
 }
 
-impl std::io::Write for BufferedWriter {
+impl<W: Write> BufferedWriter<W> {
+    /// Wraps an arbitrary [`Write`] sink (a `Vec<u8>`, a socket, a gzip
+    /// encoder...) in a [`BufWriter`].
+    pub fn from_writer(writer: W) -> Self {
+        Self {
+            buf: BufWriter::new(writer),
+        }
+    }
+}
+
+impl<W: Write> Write for BufferedWriter<W> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         self.buf.write(buf)
     }
@@ -23,6 +31,6 @@ impl std::io::Write for BufferedWriter {
         self.buf.write_vectored(bufs)
     }
     fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
+        self.buf.flush()
     }
 }